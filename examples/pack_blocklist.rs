@@ -0,0 +1,35 @@
+//! Packs the word lists in `src/blocklist/*.txt` into the `.bin` files the crate embeds, see
+//! `bundled_words` in `src/lib.rs`. Run it after changing a word list, with the lists embedded
+//! as is so it builds whatever state the `.bin` files are in:
+//!
+//! ```bash
+//! cargo run --example pack_blocklist --features uncompressed-blocklist
+//! ```
+
+use std::fs;
+
+fn main() {
+	for entry in fs::read_dir("src/blocklist").unwrap() {
+		let path = entry.unwrap().path();
+		if path.extension().is_none_or(|ext| ext != "txt") {
+			continue;
+		}
+
+		let text = fs::read_to_string(&path).unwrap();
+		let mut packed = Vec::new();
+		let mut prev = "";
+		for word in text.lines() {
+			assert!(word.is_ascii() && word > prev, "{}: {word:?} is out of order", path.display());
+			let shared = word.bytes().zip(prev.bytes()).take_while(|(a, b)| a == b).count();
+			let (shared, rest) = (shared.min(15), &word[shared.min(15)..]);
+			assert!(rest.len() < 16, "{}: {word:?} is too long", path.display());
+
+			packed.push((shared << 4 | rest.len()) as u8);
+			packed.extend(rest.bytes());
+			prev = word;
+		}
+
+		fs::write(path.with_extension("bin"), &packed).unwrap();
+		println!("{}: {} -> {} bytes", path.display(), text.len(), packed.len());
+	}
+}
@@ -0,0 +1,20 @@
+//! Writes test vectors for the default configuration as JSON, for pinning another port to this
+//! crate's output:
+//!
+//! ```bash
+//! cargo run --example test_vectors --features serde > vectors.json
+//! ```
+
+use sqids::{Sqids, VectorSpec};
+
+fn main() {
+	let sqids = Sqids::default();
+	let vectors = sqids.generate_test_vectors(VectorSpec {
+		// the default blocklist blocks several numbers just above 250,000
+		values: 250_000..=1_000_000,
+		min_lengths: vec![10, 32],
+		..VectorSpec::default()
+	});
+
+	println!("{}", serde_json::to_string_pretty(&vectors).unwrap());
+}
@@ -0,0 +1,40 @@
+# Run with `pip install .[test] && pytest python/tests`.
+import pytest
+
+from sqids import Sqids, SqidsError
+
+
+def test_simple():
+    sqids = Sqids()
+
+    assert sqids.encode([1, 2, 3]) == "86Rf07"
+    assert sqids.decode("86Rf07") == [1, 2, 3]
+
+
+def test_options():
+    sqids = Sqids(alphabet="0123456789abcdef", min_length=10, blocklist=set())
+
+    id = sqids.encode([1, 2, 3])
+    assert len(id) == 10
+    assert sqids.decode(id) == [1, 2, 3]
+
+
+def test_full_u64_range():
+    sqids = Sqids()
+
+    assert sqids.decode(sqids.encode([2**64 - 1])) == [2**64 - 1]
+
+
+def test_out_of_range_numbers():
+    sqids = Sqids()
+
+    with pytest.raises(OverflowError):
+        sqids.encode([2**64])
+    with pytest.raises(OverflowError):
+        sqids.encode([-1])
+
+
+def test_errors():
+    with pytest.raises(SqidsError):
+        Sqids(alphabet="ab")
+    assert issubclass(SqidsError, ValueError)
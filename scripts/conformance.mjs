@@ -0,0 +1,44 @@
+// Recomputes the expected output of every vector in src/conformance/ with the JavaScript
+// reference implementation. See src/conformance/README.md.
+//
+//   npm install sqids && node scripts/conformance.mjs
+
+import { readFileSync, writeFileSync } from 'node:fs';
+import Sqids from 'sqids';
+
+const dir = new URL('../src/conformance/', import.meta.url);
+
+for (const file of ['encoding', 'min_length', 'blocklist', 'invalid']) {
+	const url = new URL(`${file}.json`, dir);
+	const vectors = JSON.parse(readFileSync(url, 'utf8'));
+
+	for (const vector of vectors) {
+		let sqids;
+		try {
+			sqids = new Sqids({
+				alphabet: vector.alphabet,
+				minLength: vector.min_length,
+				blocklist: vector.blocklist && new Set(vector.blocklist),
+			});
+		} catch {
+			vector.invalid = true;
+			vector.cases = [];
+			continue;
+		}
+		delete vector.invalid;
+
+		for (const c of vector.cases) {
+			if (c.decode_only) {
+				c.numbers = sqids.decode(c.id);
+				continue;
+			}
+			try {
+				c.id = sqids.encode(c.numbers);
+			} catch {
+				c.id = null;
+			}
+		}
+	}
+
+	writeFileSync(url, JSON.stringify(vectors, null, '\t') + '\n');
+}
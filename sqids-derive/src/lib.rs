@@ -0,0 +1,71 @@
+//! `#[derive(Sqid)]`, re-exported by `sqids` with the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Index};
+
+/// Generates `to_sqid(&self, &Sqids) -> Result<String>` and
+/// `from_sqid(&Sqids, &str) -> Option<Self>` for a struct whose fields are all `Into<u64>` and
+/// `TryFrom<u64>`.
+///
+/// The fields are encoded as the numbers of the ID in declaration order, so reordering, adding
+/// or removing fields changes every ID. `from_sqid` only accepts canonical IDs (see
+/// `Sqids::decode_canonical`) with exactly one number per field, each in range for its type.
+#[proc_macro_derive(Sqid)]
+pub fn derive_sqid(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+	let fields = match &input.data {
+		Data::Struct(data) => &data.fields,
+		_ => return Err(Error::new(Span::call_site(), "Sqid can only be derived for structs")),
+	};
+	if fields.is_empty() {
+		return Err(Error::new(Span::call_site(), "Sqid needs at least one field"));
+	}
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let count = fields.len();
+
+	let members: Vec<_> = fields
+		.iter()
+		.enumerate()
+		.map(|(i, field)| match &field.ident {
+			Some(ident) => quote!(#ident),
+			None => {
+				let index = Index::from(i);
+				quote!(#index)
+			}
+		})
+		.collect();
+	let indices = 0..count;
+	let build = match fields {
+		Fields::Named(_) => {
+			quote!(Self { #(#members: ::core::convert::TryFrom::try_from(numbers[#indices]).ok()?),* })
+		}
+		_ => quote!(Self(#(::core::convert::TryFrom::try_from(numbers[#indices]).ok()?),*)),
+	};
+
+	Ok(quote! {
+		impl #impl_generics #name #ty_generics #where_clause {
+			/// Encodes the fields in declaration order.
+			pub fn to_sqid(&self, sqids: &::sqids::Sqids) -> ::sqids::Result<::std::string::String> {
+				sqids.encode(&[#(::core::convert::Into::<u64>::into(::core::clone::Clone::clone(&self.#members))),*])
+			}
+
+			/// Decodes an ID made by `to_sqid`.
+			pub fn from_sqid(sqids: &::sqids::Sqids, id: &str) -> ::core::option::Option<Self> {
+				let numbers = sqids.decode_canonical(id).ok()?;
+				if numbers.len() != #count {
+					return ::core::option::Option::None;
+				}
+
+				::core::option::Option::Some(#build)
+			}
+		}
+	})
+}
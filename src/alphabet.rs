@@ -0,0 +1,14 @@
+//! Alphabet presets. Each one passes [`crate::validate_alphabet`].
+
+/// The default alphabet: `a-z`, `A-Z` and `0-9` (62 characters).
+pub const DEFAULT: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// [`DEFAULT`] without the easily confused `0`, `O`, `1`, `l` and `I` (57 characters).
+pub const NO_LOOKALIKES: &str = "abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// [`DEFAULT`] plus `-` and `_`, the base64url character set (64 characters).
+pub const URL_SAFE: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-_";
+
+/// The Bitcoin base58 character set: `1-9`, `A-Z` without `I` and `O`, `a-z` without `l`
+/// (58 characters).
+pub const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
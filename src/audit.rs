@@ -0,0 +1,43 @@
+//! Checking that a configuration round-trips over a range of numbers.
+
+use std::{collections::HashMap, ops::Range};
+
+use crate::{Error, Sqids};
+
+/// The first problem [`Sqids::audit_range`] found.
+#[derive(Debug, Eq, PartialEq)]
+pub enum AuditReport {
+	/// `number` didn't encode.
+	EncodeFailed { number: u64, error: Error },
+	/// The ID of `number` decodes to `decoded` instead.
+	Roundtrip { number: u64, id: String, decoded: Vec<u64> },
+	/// `number` got the same ID as `first`.
+	Duplicate { number: u64, first: u64, id: String },
+}
+
+impl Sqids {
+	/// Encodes each number of `range` on its own and decodes the ID again, failing with the
+	/// first number that doesn't encode, doesn't decode back to itself or shares its ID with an
+	/// earlier one. Meant for tests of unusual configurations; it keeps every ID in memory.
+	pub fn audit_range(&self, range: Range<u64>) -> Result<(), AuditReport> {
+		let mut seen: HashMap<String, u64> = HashMap::new();
+
+		for number in range {
+			let id = self
+				.encode(&[number])
+				.map_err(|error| AuditReport::EncodeFailed { number, error })?;
+
+			let decoded = self.decode(&id);
+			if decoded != [number] {
+				return Err(AuditReport::Roundtrip { number, id, decoded });
+			}
+
+			if let Some(&first) = seen.get(&id) {
+				return Err(AuditReport::Duplicate { number, first, id });
+			}
+			seen.insert(id, number);
+		}
+
+		Ok(())
+	}
+}
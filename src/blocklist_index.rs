@@ -0,0 +1,183 @@
+//! The blocklist compiled for [`BlocklistMode`] at construction, so matching an ID doesn't loop
+//! over every word.
+
+use std::collections::HashSet;
+
+use crate::{BlocklistMode, WordSet};
+
+/// Answers "which word does this ID match" with the rules of [`BlocklistMode`], given the ID
+/// already lowercased (unless the matching is case-sensitive) like the words are. Allowlisted
+/// words are left out, since they never block.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BlocklistIndex {
+	mode: BlocklistMode,
+	/// Every word, for IDs that must equal one.
+	exact: WordSet,
+	/// Words that must start or end the ID, and their distinct byte lengths in ascending order.
+	affixes: WordSet,
+	affix_lengths: Vec<usize>,
+	/// Words that may appear anywhere in the ID.
+	substrings: Automaton,
+}
+
+impl BlocklistIndex {
+	pub(crate) fn new(
+		blocklist: &HashSet<String>,
+		mode: BlocklistMode,
+		allowlist: &HashSet<String>,
+	) -> Self {
+		let mut index = BlocklistIndex { mode, ..BlocklistIndex::default() };
+		if mode == BlocklistMode::Off {
+			return index;
+		}
+
+		let mut substrings = Vec::new();
+		for word in blocklist.iter().filter(|word| !allowlist.contains(*word)) {
+			index.exact.insert(word.clone());
+			match mode {
+				BlocklistMode::Off | BlocklistMode::Exact => {}
+				BlocklistMode::PrefixSuffix => {
+					index.affixes.insert(word.clone());
+				}
+				// words of up to 3 characters only ever match IDs equal to them
+				BlocklistMode::Substring if word.chars().count() <= 3 => {}
+				BlocklistMode::Substring if word.chars().any(|c| c.is_ascii_digit()) => {
+					index.affixes.insert(word.clone());
+				}
+				BlocklistMode::Substring => substrings.push(word.as_str()),
+			}
+		}
+
+		index.affix_lengths = index.affixes.iter().map(String::len).collect();
+		index.affix_lengths.sort_unstable();
+		index.affix_lengths.dedup();
+		index.substrings = Automaton::new(&substrings);
+		index
+	}
+
+	/// The first word `id` matches, or `None`.
+	pub(crate) fn matching_word(&self, id: &str) -> Option<&str> {
+		if let Some(word) = self.exact.get(id) {
+			return Some(word);
+		}
+
+		match self.mode {
+			BlocklistMode::Off | BlocklistMode::Exact => None,
+			// IDs of up to 3 characters must equal a word
+			BlocklistMode::Substring if id.chars().nth(3).is_none() => None,
+			BlocklistMode::PrefixSuffix | BlocklistMode::Substring => {
+				self.matching_affix(id).or_else(|| self.substrings.find(id))
+			}
+		}
+	}
+
+	fn matching_affix(&self, id: &str) -> Option<&str> {
+		self.affix_lengths.iter().take_while(|&&len| len <= id.len()).find_map(|&len| {
+			let end = id.len() - len;
+			let prefix = id.is_char_boundary(len).then(|| self.affixes.get(&id[..len])).flatten();
+			let suffix =
+				|| id.is_char_boundary(end).then(|| self.affixes.get(&id[end..])).flatten();
+			prefix.or_else(suffix).map(String::as_str)
+		})
+	}
+}
+
+/// An Aho-Corasick automaton over the UTF-8 bytes of the words, which finds a word in an ID in
+/// one pass over the ID. Byte substrings of valid UTF-8 are character substrings, so this agrees
+/// with `str::contains`.
+#[derive(Clone, Debug, Default)]
+struct Automaton {
+	words: Vec<String>,
+	states: Vec<State>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct State {
+	/// Transitions sorted by byte.
+	next: Vec<(u8, usize)>,
+	/// The state of the longest proper suffix of this state's path that is in the trie.
+	fail: usize,
+	/// A word ending here or at a state down the `fail` chain.
+	word: Option<usize>,
+}
+
+impl State {
+	fn next(&self, byte: u8) -> Option<usize> {
+		let i = self.next.binary_search_by_key(&byte, |&(b, _)| b).ok()?;
+		Some(self.next[i].1)
+	}
+}
+
+impl Automaton {
+	fn new(words: &[&str]) -> Self {
+		if words.is_empty() {
+			return Automaton::default();
+		}
+
+		let mut states = vec![State::default()];
+		for (i, word) in words.iter().enumerate() {
+			let mut state = 0;
+			for byte in word.bytes() {
+				state = match states[state].next(byte) {
+					Some(next) => next,
+					None => {
+						states.push(State::default());
+						let next = states.len() - 1;
+						let transitions = &mut states[state].next;
+						let at = transitions.partition_point(|&(b, _)| b < byte);
+						transitions.insert(at, (byte, next));
+						next
+					}
+				};
+			}
+			states[state].word.get_or_insert(i);
+		}
+
+		// breadth-first, so the states a `fail` link can point to are done first
+		let mut queue: Vec<usize> = states[0].next.iter().map(|&(_, next)| next).collect();
+		let mut head = 0;
+		while let Some(&state) = queue.get(head) {
+			head += 1;
+			for (byte, next) in states[state].next.clone() {
+				let mut fail = states[state].fail;
+				let fail = loop {
+					if let Some(target) = states[fail].next(byte) {
+						break target;
+					}
+					if fail == 0 {
+						break 0;
+					}
+					fail = states[fail].fail;
+				};
+				states[next].fail = fail;
+				states[next].word = states[next].word.or(states[fail].word);
+				queue.push(next);
+			}
+		}
+
+		Automaton { words: words.iter().map(|word| word.to_string()).collect(), states }
+	}
+
+	fn find(&self, id: &str) -> Option<&str> {
+		if self.states.is_empty() {
+			return None;
+		}
+
+		let mut state = 0;
+		for byte in id.bytes() {
+			state = loop {
+				if let Some(next) = self.states[state].next(byte) {
+					break next;
+				}
+				if state == 0 {
+					break 0;
+				}
+				state = self.states[state].fail;
+			};
+			if let Some(word) = self.states[state].word {
+				return Some(&self.words[word]);
+			}
+		}
+		None
+	}
+}
@@ -0,0 +1,130 @@
+//! Loading [`Options`] from TOML or JSON files, enabled with the `config` feature.
+//!
+//! A config file has the same fields as [`Options`] (missing ones take the default value) plus
+//! `blocklist_path`, a file read with [`Options::blocklist_from_path`] and used instead of an
+//! inline `blocklist`. Unknown fields are rejected, and so is `blocklist_path` without the
+//! `blocklist` feature. See `examples/config/sqids.toml`.
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use crate::{Error, Options, Result};
+
+struct ConfigFile {
+	options: Options,
+	blocklist_path: Option<PathBuf>,
+}
+
+impl ConfigFile {
+	/// `blocklist_path` isn't an `Options` field, so it is taken out of the document before the
+	/// rest is deserialized (which rejects unknown fields).
+	fn new(has_blocklist: bool, blocklist_path: Option<PathBuf>, options: Options) -> Result<Self> {
+		if has_blocklist && blocklist_path.is_some() {
+			return Err(Error::Config(
+				"blocklist and blocklist_path cannot both be set".to_string(),
+			));
+		}
+
+		Ok(ConfigFile { options, blocklist_path })
+	}
+
+	/// Resolves the blocklist, with a relative `blocklist_path` taken relative to `dir`.
+	#[cfg(feature = "blocklist")]
+	fn into_options(self, dir: Option<&Path>) -> Result<Options> {
+		let mut options = self.options;
+
+		if let Some(path) = self.blocklist_path {
+			let path = match dir {
+				Some(dir) if path.is_relative() => dir.join(path),
+				_ => path,
+			};
+			options.blocklist = Options::blocklist_from_path(&path)?;
+		}
+
+		Ok(options)
+	}
+
+	#[cfg(not(feature = "blocklist"))]
+	fn into_options(self, _dir: Option<&Path>) -> Result<Options> {
+		match self.blocklist_path {
+			Some(_) => {
+				Err(Error::Config("blocklist_path requires the blocklist feature".to_string()))
+			}
+			None => Ok(self.options),
+		}
+	}
+}
+
+fn invalid_blocklist_path() -> Error {
+	Error::Config("blocklist_path must be a string".to_string())
+}
+
+impl Options {
+	/// Parses options from a TOML document. A relative `blocklist_path` is resolved against
+	/// the current directory.
+	pub fn from_toml_str(s: &str) -> Result<Self> {
+		Self::parse_toml(s)?.into_options(None)
+	}
+
+	/// Parses options from a JSON document. A relative `blocklist_path` is resolved against
+	/// the current directory.
+	pub fn from_json_str(s: &str) -> Result<Self> {
+		Self::parse_json(s)?.into_options(None)
+	}
+
+	/// Reads options from a `.toml` or `.json` file. A relative `blocklist_path` is resolved
+	/// against the directory of the config file.
+	pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+		let path = path.as_ref();
+		let contents = fs::read_to_string(path)
+			.map_err(|err| Error::Config(format!("could not read {}: {err}", path.display())))?;
+
+		let config = match path.extension().and_then(|ext| ext.to_str()) {
+			Some("toml") => Self::parse_toml(&contents)?,
+			Some("json") => Self::parse_json(&contents)?,
+			_ => {
+				return Err(Error::Config(format!(
+					"unsupported config file extension: {}",
+					path.display()
+				)))
+			}
+		};
+
+		config.into_options(path.parent())
+	}
+
+	fn parse_toml(s: &str) -> Result<ConfigFile> {
+		let mut table: toml::Table =
+			toml::from_str(s).map_err(|err| Error::Config(err.to_string()))?;
+
+		let blocklist_path = match table.remove("blocklist_path") {
+			Some(toml::Value::String(path)) => Some(PathBuf::from(path)),
+			Some(_) => return Err(invalid_blocklist_path()),
+			None => None,
+		};
+		let has_blocklist = table.contains_key("blocklist");
+		let options = toml::Value::Table(table)
+			.try_into()
+			.map_err(|err: toml::de::Error| Error::Config(err.to_string()))?;
+
+		ConfigFile::new(has_blocklist, blocklist_path, options)
+	}
+
+	fn parse_json(s: &str) -> Result<ConfigFile> {
+		let mut map: serde_json::Map<String, serde_json::Value> =
+			serde_json::from_str(s).map_err(|err| Error::Config(err.to_string()))?;
+
+		let blocklist_path = match map.remove("blocklist_path") {
+			Some(serde_json::Value::String(path)) => Some(PathBuf::from(path)),
+			Some(_) => return Err(invalid_blocklist_path()),
+			None => None,
+		};
+		let has_blocklist = map.contains_key("blocklist");
+		let options = serde_json::from_value(serde_json::Value::Object(map))
+			.map_err(|err| Error::Config(err.to_string()))?;
+
+		ConfigFile::new(has_blocklist, blocklist_path, options)
+	}
+}
@@ -0,0 +1,114 @@
+//! The Sqids spec test vectors, enabled with the `conformance` feature, for checking that this
+//! crate (or a configuration layer built on it) produces the same IDs as the reference
+//! implementation.
+//!
+//! The vectors are vendored as JSON in `src/conformance/`, one file per category. Their expected
+//! IDs come from the spec's test suite; `scripts/conformance.mjs` recomputes them with the
+//! JavaScript reference implementation, see `src/conformance/README.md`.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+
+use crate::{Options, Sqids};
+
+const FILES: [(&str, &str); 4] = [
+	("encoding", include_str!("conformance/encoding.json")),
+	("min_length", include_str!("conformance/min_length.json")),
+	("blocklist", include_str!("conformance/blocklist.json")),
+	("invalid", include_str!("conformance/invalid.json")),
+];
+
+/// One configuration and the IDs it must produce.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Vector {
+	/// The file the vector comes from: `encoding`, `min_length`, `blocklist` or `invalid`.
+	#[serde(skip)]
+	pub category: &'static str,
+	pub name: String,
+	/// The default alphabet if `None`.
+	pub alphabet: Option<String>,
+	pub min_length: Option<u8>,
+	/// The default blocklist if `None`.
+	pub blocklist: Option<HashSet<String>>,
+	/// Whether the configuration must be rejected.
+	#[serde(default)]
+	pub invalid: bool,
+	pub cases: Vec<Case>,
+}
+
+/// `numbers` encode to `id` and `id` decodes to `numbers`. With `decode_only` only the latter is
+/// checked; a `None` id means encoding must fail.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Case {
+	pub numbers: Vec<u64>,
+	pub id: Option<String>,
+	#[serde(default)]
+	pub decode_only: bool,
+}
+
+/// All vendored vectors.
+pub fn vectors() -> Vec<Vector> {
+	FILES
+		.iter()
+		.flat_map(|&(category, json)| {
+			let vectors: Vec<Vector> = serde_json::from_str(json).unwrap();
+			vectors.into_iter().map(move |vector| Vector { category, ..vector })
+		})
+		.collect()
+}
+
+/// Checks every vector, returning a description of each mismatch.
+pub fn check_all() -> Vec<String> {
+	vectors().iter().filter_map(|vector| vector.check().err()).collect()
+}
+
+impl Vector {
+	pub fn options(&self) -> Options {
+		Options::new(self.alphabet.clone(), self.min_length, self.blocklist.clone())
+	}
+
+	/// Builds a `Sqids` from [`Vector::options`] and checks every case against it.
+	pub fn check(&self) -> Result<(), String> {
+		let name = format!("{}/{}", self.category, self.name);
+
+		let sqids = match (Sqids::new(Some(self.options())), self.invalid) {
+			(Ok(sqids), false) => sqids,
+			(Err(_), true) => return Ok(()),
+			(Ok(_), true) => return Err(format!("{name}: options were accepted")),
+			(Err(err), false) => return Err(format!("{name}: options were rejected: {err}")),
+		};
+
+		for case in &self.cases {
+			let numbers = &case.numbers;
+			let Some(id) = &case.id else {
+				if let Ok(got) = sqids.encode(numbers) {
+					return Err(format!(
+						"{name}: encode({numbers:?}) = {got:?}, expected an error"
+					));
+				}
+				continue;
+			};
+
+			if !case.decode_only {
+				match sqids.encode(numbers) {
+					Ok(got) if got == *id => {}
+					got => {
+						return Err(format!(
+							"{name}: encode({numbers:?}) = {got:?}, expected {id:?}"
+						))
+					}
+				}
+			}
+
+			let got = sqids.decode(id);
+			if got != *numbers {
+				return Err(format!("{name}: decode({id:?}) = {got:?}, expected {numbers:?}"));
+			}
+		}
+
+		Ok(())
+	}
+}
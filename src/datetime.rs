@@ -0,0 +1,31 @@
+//! Timestamped IDs, enabled with the `chrono` feature.
+
+use chrono::{DateTime, Utc};
+
+use crate::{Error, Result, Sqids};
+
+impl Sqids {
+	/// Encodes the Unix timestamp of `dt` in milliseconds followed by `extra`. Timestamps before
+	/// the epoch return [`Error::TimestampBeforeEpoch`].
+	pub fn encode_datetime(&self, dt: DateTime<Utc>, extra: &[u64]) -> Result<String> {
+		let millis =
+			u64::try_from(dt.timestamp_millis()).map_err(|_| Error::TimestampBeforeEpoch)?;
+
+		let mut numbers = Vec::with_capacity(extra.len() + 1);
+		numbers.push(millis);
+		numbers.extend_from_slice(extra);
+		self.encode(&numbers)
+	}
+
+	/// Splits an ID from [`Sqids::encode_datetime`] back into the timestamp and the extra numbers.
+	/// Returns `None` if the ID is invalid or its first number isn't a representable timestamp.
+	pub fn decode_datetime(&self, id: &str) -> Option<(DateTime<Utc>, Vec<u64>)> {
+		let mut numbers = self.decode(id);
+		if numbers.is_empty() {
+			return None;
+		}
+
+		let millis = i64::try_from(numbers.remove(0)).ok()?;
+		Some((DateTime::from_timestamp_millis(millis)?, numbers))
+	}
+}
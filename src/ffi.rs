@@ -0,0 +1,199 @@
+//! C-compatible bindings, enabled with the `ffi` feature.
+//!
+//! A `Sqids` is handed out as an opaque pointer from [`sqids_new`] and must be released with
+//! [`sqids_free`]. Fallible functions return one of the `SQIDS_*` status codes. The header can be
+//! generated with `cbindgen --config cbindgen.toml --output sqids.h`, and the static library
+//! built with `cargo rustc --lib --release --features ffi --crate-type staticlib`.
+
+use std::{
+	ffi::{c_char, c_int, CStr},
+	ptr, slice,
+};
+
+use crate::{Error, Options, Sqids};
+
+pub const SQIDS_OK: c_int = 0;
+pub const SQIDS_ERROR_NULL_POINTER: c_int = -1;
+pub const SQIDS_ERROR_INVALID_UTF8: c_int = -2;
+pub const SQIDS_ERROR_BUFFER_TOO_SMALL: c_int = -3;
+pub const SQIDS_ERROR_ALPHABET_MULTIBYTE_CHARACTERS: c_int = -4;
+pub const SQIDS_ERROR_ALPHABET_LENGTH: c_int = -5;
+pub const SQIDS_ERROR_ALPHABET_UNIQUE_CHARACTERS: c_int = -6;
+pub const SQIDS_ERROR_BLOCKLIST_MAX_ATTEMPTS: c_int = -7;
+pub const SQIDS_ERROR_MAX_LENGTH_BELOW_MIN_LENGTH: c_int = -8;
+pub const SQIDS_ERROR_MAX_LENGTH_EXCEEDED: c_int = -9;
+/// An error the C API can't produce, reserved for future use.
+pub const SQIDS_ERROR_OTHER: c_int = -100;
+
+fn status(err: &Error) -> c_int {
+	match err {
+		Error::AlphabetMultibyteCharacters => SQIDS_ERROR_ALPHABET_MULTIBYTE_CHARACTERS,
+		Error::AlphabetLength => SQIDS_ERROR_ALPHABET_LENGTH,
+		Error::AlphabetUniqueCharacters => SQIDS_ERROR_ALPHABET_UNIQUE_CHARACTERS,
+		Error::BlocklistMaxAttempts { .. } => SQIDS_ERROR_BLOCKLIST_MAX_ATTEMPTS,
+		Error::MaxLengthBelowMinLength => SQIDS_ERROR_MAX_LENGTH_BELOW_MIN_LENGTH,
+		Error::MaxLengthExceeded => SQIDS_ERROR_MAX_LENGTH_EXCEEDED,
+		_ => SQIDS_ERROR_OTHER,
+	}
+}
+
+/// Creates a `Sqids` with the given alphabet (or the default one if `alphabet` is null) and the
+/// default blocklist. Returns null if the alphabet is invalid; [`sqids_validate_alphabet`] tells
+/// why.
+///
+/// # Safety
+///
+/// `alphabet` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sqids_new(alphabet: *const c_char, min_length: u8) -> *mut Sqids {
+	let alphabet = if alphabet.is_null() {
+		None
+	} else {
+		match CStr::from_ptr(alphabet).to_str() {
+			Ok(alphabet) => Some(alphabet.to_string()),
+			Err(_) => return ptr::null_mut(),
+		}
+	};
+
+	match Sqids::new(Some(Options::new(alphabet, Some(min_length), None))) {
+		Ok(sqids) => Box::into_raw(Box::new(sqids)),
+		Err(_) => ptr::null_mut(),
+	}
+}
+
+/// Returns [`SQIDS_OK`] if `alphabet` is usable by [`sqids_new`], or the reason it is not.
+///
+/// # Safety
+///
+/// `alphabet` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sqids_validate_alphabet(alphabet: *const c_char) -> c_int {
+	if alphabet.is_null() {
+		return SQIDS_ERROR_NULL_POINTER;
+	}
+
+	match CStr::from_ptr(alphabet).to_str() {
+		Ok(alphabet) => match crate::validate_alphabet(alphabet) {
+			Ok(()) => SQIDS_OK,
+			Err(err) => status(&err),
+		},
+		Err(_) => SQIDS_ERROR_INVALID_UTF8,
+	}
+}
+
+/// Returns the length of the ID [`sqids_encode`] would produce, not counting the NUL
+/// terminator.
+///
+/// # Safety
+///
+/// `sqids` must come from [`sqids_new`] and `nums` must point to `len` numbers (or be null if
+/// `len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn sqids_encoded_length(
+	sqids: *const Sqids,
+	nums: *const u64,
+	len: usize,
+) -> usize {
+	match (sqids.as_ref(), numbers(nums, len)) {
+		(Some(sqids), Some(numbers)) => sqids.estimate_length(numbers),
+		_ => 0,
+	}
+}
+
+/// Encodes `len` numbers into `out` as a NUL-terminated string. Returns
+/// [`SQIDS_ERROR_BUFFER_TOO_SMALL`] if the ID and its terminator don't fit in `out_cap` bytes.
+///
+/// # Safety
+///
+/// `sqids` must come from [`sqids_new`], `nums` must point to `len` numbers (or be null if `len`
+/// is 0) and `out` must point to `out_cap` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sqids_encode(
+	sqids: *const Sqids,
+	nums: *const u64,
+	len: usize,
+	out: *mut c_char,
+	out_cap: usize,
+) -> c_int {
+	let (Some(sqids), Some(numbers)) = (sqids.as_ref(), numbers(nums, len)) else {
+		return SQIDS_ERROR_NULL_POINTER;
+	};
+	if out.is_null() {
+		return SQIDS_ERROR_NULL_POINTER;
+	}
+
+	let id = match sqids.encode(numbers) {
+		Ok(id) => id,
+		Err(err) => return status(&err),
+	};
+	if id.len() >= out_cap {
+		return SQIDS_ERROR_BUFFER_TOO_SMALL;
+	}
+
+	ptr::copy_nonoverlapping(id.as_ptr(), out as *mut u8, id.len());
+	*out.add(id.len()) = 0;
+
+	SQIDS_OK
+}
+
+/// Decodes `id` into `out`, storing the count of decoded numbers in `out_len`. Returns
+/// [`SQIDS_ERROR_BUFFER_TOO_SMALL`] if there are more than `out_cap` numbers, in which case
+/// `out_len` still receives the required count. An invalid ID decodes to zero numbers.
+///
+/// # Safety
+///
+/// `sqids` must come from [`sqids_new`], `id` must point to a NUL-terminated string, `out` must
+/// point to `out_cap` writable numbers and `out_len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn sqids_decode(
+	sqids: *const Sqids,
+	id: *const c_char,
+	out: *mut u64,
+	out_cap: usize,
+	out_len: *mut usize,
+) -> c_int {
+	let Some(sqids) = sqids.as_ref() else {
+		return SQIDS_ERROR_NULL_POINTER;
+	};
+	if id.is_null() || out_len.is_null() || (out.is_null() && out_cap > 0) {
+		return SQIDS_ERROR_NULL_POINTER;
+	}
+	let Ok(id) = CStr::from_ptr(id).to_str() else {
+		return SQIDS_ERROR_INVALID_UTF8;
+	};
+
+	let numbers = sqids.decode(id);
+	*out_len = numbers.len();
+	if numbers.len() > out_cap {
+		return SQIDS_ERROR_BUFFER_TOO_SMALL;
+	}
+
+	if !numbers.is_empty() {
+		ptr::copy_nonoverlapping(numbers.as_ptr(), out, numbers.len());
+	}
+
+	SQIDS_OK
+}
+
+/// Releases a `Sqids` created by [`sqids_new`]. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `sqids` must be null or come from [`sqids_new`], and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn sqids_free(sqids: *mut Sqids) {
+	if !sqids.is_null() {
+		drop(Box::from_raw(sqids));
+	}
+}
+
+unsafe fn numbers<'a>(nums: *const u64, len: usize) -> Option<&'a [u64]> {
+	if len == 0 {
+		return Some(&[]);
+	}
+	if nums.is_null() {
+		return None;
+	}
+
+	Some(slice::from_raw_parts(nums, len))
+}
@@ -0,0 +1,218 @@
+//! A `Sqids` for small, fixed alphabets that encodes and decodes without heap allocations, with
+//! the `fixed` feature.
+
+use std::cmp::min;
+
+use arrayvec::{ArrayString, ArrayVec};
+
+use crate::{shuffle_chars, to_number, Error, Result};
+
+/// Like [`Sqids`](crate::Sqids) with the same alphabet, `min_length` and blocklist, generating
+/// the same IDs, but the alphabet is an array and IDs and numbers go into fixed-capacity
+/// buffers, so neither `encode` nor `decode` allocates (only the error for an exhausted
+/// blocklist does). The other options aren't supported.
+///
+/// The alphabet must be ASCII, so the capacity of an ID is its maximum length.
+///
+/// ```
+/// # use sqids::SqidsN;
+/// let sqids = SqidsN::new(b"0123456789abcdef".map(char::from), 8, &[]).unwrap();
+/// let id = sqids.encode::<16>(&[1, 2, 3]).unwrap();
+/// assert_eq!(sqids.decode::<4>(&id).as_slice(), [1, 2, 3]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SqidsN<const N: usize> {
+	alphabet: [char; N],
+	min_length: u8,
+	blocklist: &'static [&'static str],
+}
+
+impl<const N: usize> SqidsN<N> {
+	/// Fails like [`Sqids::new`](crate::Sqids::new) for alphabets shorter than 3 characters, with
+	/// repeated characters or with non-ASCII characters. The blocklist is matched ASCII
+	/// case-insensitively with the spec's rules, ignoring words shorter than 3 characters.
+	pub fn new(
+		alphabet: [char; N],
+		min_length: u8,
+		blocklist: &'static [&'static str],
+	) -> Result<Self> {
+		if !alphabet.iter().all(char::is_ascii) {
+			return Err(Error::AlphabetMultibyteCharacters);
+		}
+
+		if N < 3 {
+			return Err(Error::AlphabetLength);
+		}
+
+		if (1..N).any(|i| alphabet[..i].contains(&alphabet[i])) {
+			return Err(Error::AlphabetUniqueCharacters);
+		}
+
+		let mut alphabet = alphabet;
+		shuffle_chars(&mut alphabet);
+
+		Ok(SqidsN { alphabet, min_length, blocklist })
+	}
+
+	/// Encodes into an ID of at most `CAP` characters, failing with
+	/// [`Error::MaxLengthExceeded`] if it doesn't fit.
+	pub fn encode<const CAP: usize>(&self, numbers: &[u64]) -> Result<ArrayString<CAP>> {
+		if numbers.is_empty() {
+			return Ok(ArrayString::new());
+		}
+
+		let mut increment = 0;
+		loop {
+			let id = self.generate_id(numbers, increment)?;
+
+			let Some(word) = self.blocked_word(&id) else {
+				return Ok(id);
+			};
+
+			if increment >= N {
+				return Err(Error::BlocklistMaxAttempts {
+					attempts: increment + 1,
+					word: word.to_string(),
+				});
+			}
+			increment += 1;
+		}
+	}
+
+	/// Decodes up to `M` numbers. Like [`Sqids::decode`](crate::Sqids::decode), invalid IDs give
+	/// no numbers, and so do IDs with more than `M` numbers.
+	pub fn decode<const M: usize>(&self, id: &str) -> ArrayVec<u64, M> {
+		let mut ret = ArrayVec::new();
+
+		let Some(prefix) = id.chars().next() else {
+			return ret;
+		};
+		if !id.chars().all(|c| self.alphabet.contains(&c)) {
+			return ret;
+		}
+
+		let offset = self.alphabet.iter().position(|&c| c == prefix).unwrap();
+		let mut alphabet = self.alphabet;
+		alphabet.rotate_left(offset);
+		alphabet.reverse();
+
+		let mut id = &id[1..];
+		while !id.is_empty() {
+			let separator = alphabet[0];
+			let (chunk, rest) = match id.split_once(separator) {
+				Some((chunk, rest)) => (chunk, Some(rest)),
+				None => (id, None),
+			};
+			if chunk.is_empty() {
+				break;
+			}
+
+			match to_number(chunk, &alphabet[1..]) {
+				Some(number) if ret.try_push(number).is_ok() => {}
+				_ => return ArrayVec::new(),
+			}
+
+			match rest {
+				Some(rest) => {
+					shuffle_chars(&mut alphabet);
+					id = rest;
+				}
+				None => break,
+			}
+		}
+
+		ret
+	}
+
+	fn generate_id<const CAP: usize>(
+		&self,
+		numbers: &[u64],
+		increment: usize,
+	) -> Result<ArrayString<CAP>> {
+		let offset = numbers
+			.iter()
+			.enumerate()
+			.fold(numbers.len(), |a, (i, &v)| self.alphabet[v as usize % N] as usize + i + a)
+			% N;
+
+		let mut alphabet = self.alphabet;
+		alphabet.rotate_left((offset + increment) % N);
+		let prefix = alphabet[0];
+		alphabet.reverse();
+
+		let mut id = ArrayString::new();
+		push(&mut id, prefix)?;
+
+		for (i, &num) in numbers.iter().enumerate() {
+			push_number(&mut id, num, &alphabet[1..])?;
+
+			if i < numbers.len() - 1 {
+				push(&mut id, alphabet[0])?;
+				shuffle_chars(&mut alphabet);
+			}
+		}
+
+		let min_length = self.min_length as usize;
+		if min_length > id.len() {
+			push(&mut id, alphabet[0])?;
+
+			while min_length > id.len() {
+				shuffle_chars(&mut alphabet);
+
+				let slice_len = min(min_length - id.len(), N);
+				for &c in &alphabet[..slice_len] {
+					push(&mut id, c)?;
+				}
+			}
+		}
+
+		Ok(id)
+	}
+
+	fn blocked_word(&self, id: &str) -> Option<&'static str> {
+		let id = id.as_bytes();
+
+		self.blocklist.iter().copied().find(|word| {
+			let word = word.as_bytes();
+			if word.len() < 3 || word.len() > id.len() {
+				return false;
+			}
+
+			if id.len() <= 3 || word.len() <= 3 {
+				id.eq_ignore_ascii_case(word)
+			} else if word.iter().any(u8::is_ascii_digit) {
+				id[..word.len()].eq_ignore_ascii_case(word)
+					|| id[id.len() - word.len()..].eq_ignore_ascii_case(word)
+			} else {
+				id.windows(word.len()).any(|window| window.eq_ignore_ascii_case(word))
+			}
+		})
+	}
+}
+
+fn push<const CAP: usize>(id: &mut ArrayString<CAP>, c: char) -> Result<()> {
+	id.try_push(c).map_err(|_| Error::MaxLengthExceeded)
+}
+
+/// [`to_id`](crate::to_id) into `id`, with the digits on the stack.
+fn push_number<const CAP: usize>(
+	id: &mut ArrayString<CAP>,
+	num: u64,
+	alphabet: &[char],
+) -> Result<()> {
+	// a u64 has at most 64 digits in base 2
+	let mut digits = ['\0'; 64];
+	let mut len = 0;
+	let mut result = num;
+	loop {
+		digits[len] = alphabet[(result % alphabet.len() as u64) as usize];
+		len += 1;
+		result /= alphabet.len() as u64;
+
+		if result == 0 {
+			break;
+		}
+	}
+
+	digits[..len].iter().rev().try_for_each(|&c| push(id, c))
+}
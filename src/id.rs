@@ -0,0 +1,70 @@
+//! A process-wide `Sqids`, and a typed ID that encodes and decodes itself with it.
+//!
+//! The `Sqids` is installed once with [`set_default`], before the first use of
+//! [`default_instance`] or the first ID is formatted or parsed; without it `Sqids::default()` is
+//! used. This suits applications with a single
+//! configuration: there is one instance per process (tests in the same binary share it), it can
+//! never be replaced, and code that needs several configurations should use `Sqids` directly.
+
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+use crate::{Error, Result, Sqids};
+
+static DEFAULT: OnceLock<Sqids> = OnceLock::new();
+
+/// Installs the process-wide `Sqids`. Fails with [`Error::DefaultAlreadySet`] if one was already
+/// installed, or if the default was used before and got locked in.
+pub fn set_default(sqids: Sqids) -> Result<()> {
+	DEFAULT.set(sqids).map_err(|_| Error::DefaultAlreadySet)
+}
+
+/// The process-wide `Sqids`, `Sqids::default()` unless [`set_default`] installed another one.
+pub fn default_instance() -> &'static Sqids {
+	DEFAULT.get_or_init(Sqids::default)
+}
+
+/// Numbers that format as their ID and parse back from it.
+///
+/// `Display` fails with [`fmt::Error`] when encoding does (blocklist exhausted or `max_length`
+/// exceeded), which makes `to_string()` and `format!` panic; call `try_to_string` to get the
+/// error instead.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SqidId(pub Vec<u64>);
+
+impl SqidId {
+	/// Same as [`set_default`].
+	pub fn set_default(sqids: Sqids) -> Result<()> {
+		set_default(sqids)
+	}
+
+	/// The `Sqids` every `SqidId` uses, [`default_instance`].
+	pub fn sqids() -> &'static Sqids {
+		default_instance()
+	}
+
+	/// Encodes the numbers, like `to_string()` but without panicking.
+	pub fn try_to_string(&self) -> Result<String> {
+		Self::sqids().encode(&self.0)
+	}
+}
+
+impl From<Vec<u64>> for SqidId {
+	fn from(numbers: Vec<u64>) -> Self {
+		SqidId(numbers)
+	}
+}
+
+impl fmt::Display for SqidId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.try_to_string().map_err(|_| fmt::Error)?)
+	}
+}
+
+impl FromStr for SqidId {
+	type Err = Error;
+
+	/// Only accepts the canonical ID of its numbers, the one `Display` produces.
+	fn from_str(id: &str) -> Result<Self> {
+		Self::sqids().decode_canonical(id).map(SqidId)
+	}
+}
@@ -1,33 +1,761 @@
-use std::{cmp::min, collections::HashSet, result};
+use std::{
+	borrow::Cow,
+	cell::RefCell,
+	cmp::min,
+	collections::{HashMap, HashSet},
+	env, fmt,
+	hash::{Hash, Hasher},
+	io::{self, Write},
+	ops::Range,
+	result,
+	str::FromStr,
+	sync::Arc,
+};
+#[cfg(feature = "blocklist")]
+use std::{fs, io::BufRead, path::Path, sync::OnceLock};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+#[cfg(feature = "blocklist")]
+use blocklist_index::BlocklistIndex;
+
+pub mod alphabet;
+mod audit;
+#[cfg(feature = "blocklist")]
+mod blocklist_index;
+#[cfg(feature = "config")]
+mod config;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+#[cfg(feature = "chrono")]
+mod datetime;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fixed")]
+mod fixed;
+mod id;
+mod packed;
+pub mod presets;
+#[cfg(feature = "python")]
+pub mod python;
+mod registry;
+mod ring;
+mod vectors;
+mod versioned;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use audit::AuditReport;
+#[cfg(feature = "fixed")]
+pub use fixed::SqidsN;
+pub use id::{default_instance, set_default, SqidId};
+pub use registry::SqidsRegistry;
+pub use ring::SqidsRing;
+#[cfg(feature = "derive")]
+pub use sqids_derive::Sqid;
+pub use vectors::{TestVector, VectorSpec};
+pub use versioned::VersionedSqids;
+
+/// New variants may be added in minor releases, so matches need a wildcard arm.
 #[derive(Error, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
-	#[error("Alphabet cannot contain multibyte characters")]
+	#[error("Alphabet cannot contain multibyte characters unless allow_multibyte is set")]
 	AlphabetMultibyteCharacters,
 	#[error("Alphabet length must be at least 3")]
 	AlphabetLength,
 	#[error("Alphabet must contain unique characters")]
 	AlphabetUniqueCharacters,
-	#[error("Reached max attempts to re-generate the ID")]
-	BlocklistMaxAttempts,
+	#[error("Reached max attempts to re-generate the ID ({attempts} attempts, last blocked by {word:?})")]
+	BlocklistMaxAttempts { attempts: usize, word: String },
+	#[error("Alphabet characters {characters:?} are not allowed by the {policy} policy")]
+	AlphabetPolicyViolation { policy: &'static str, characters: Vec<char> },
+	#[error("Excluded characters must be part of the alphabet")]
+	AlphabetExcludedCharacters,
+	#[error("Environment variable {name} has an invalid value: {value:?}")]
+	EnvVar { name: String, value: String },
+	#[error("Could not read blocklist file {path}: {reason}")]
+	BlocklistFile { path: String, reason: String },
+	#[error("Could not read blocklist line {line}: {reason}")]
+	BlocklistLine { line: usize, reason: String },
+	#[error("Invalid configuration: {0}")]
+	Config(String),
+	#[error("Max length cannot be less than min length")]
+	MaxLengthBelowMinLength,
+	#[error("Fixed length must be between min length and max length")]
+	FixedLengthOutOfRange,
+	#[error("Generated ID exceeds max length")]
+	MaxLengthExceeded,
+	#[error("Generated ID does not decode back into the encoded numbers")]
+	RoundtripFailed,
+	#[error("Not a valid ID for this configuration")]
+	InvalidId,
+	#[error("Alphabet contains both cases of {0:?}, so it cannot be decoded case-insensitively")]
+	AlphabetCaseConflict(char),
+	#[error("Padding character must be ASCII and not in the alphabet")]
+	InvalidPaddingChar,
+	#[error("Group size must be positive and the joiner must not be in the alphabet")]
+	InvalidGroup,
+	#[error("Shuffle rounds must be positive")]
+	InvalidShuffleRounds,
+	#[error("Prefix must not end with a character of the alphabet")]
+	InvalidPrefix,
+	#[error("ID does not start with the configured prefix")]
+	PrefixMismatch,
+	#[error("Prefix {prefix:?} is ambiguous with the registered prefix {existing:?}")]
+	AmbiguousPrefix { prefix: String, existing: String },
+	#[error("No Sqids is registered for prefix {0:?}")]
+	UnknownPrefix(String),
+	#[error("No configuration for the version tagged {0:?}")]
+	UnknownVersion(char),
+	#[error("Version {0} is out of range, versions must be below the length of their alphabet")]
+	VersionOutOfRange(u8),
+	#[error("Version {0} has no configuration")]
+	MissingVersion(u8),
+	#[error("Versions {first} and {second} are both tagged {tag:?}")]
+	AmbiguousVersionTag { tag: char, first: u8, second: u8 },
+	#[error("Numbers are not sorted in non-decreasing order")]
+	NotSorted,
+	#[error("Expected an ID of {expected} number(s), got {actual}")]
+	UnexpectedArity { expected: usize, actual: usize },
+	#[error("The default Sqids is already set or in use")]
+	DefaultAlreadySet,
+	#[error("Timestamp is before the Unix epoch")]
+	TimestampBeforeEpoch,
+	#[error("Homoglyph {from:?} -> {to:?} must map a character outside the alphabet to one in it")]
+	InvalidHomoglyph { from: char, to: char },
+	#[error("The alphabet permutation must only reorder the characters")]
+	InvalidPermutation,
+	#[error("Cannot encode an empty list of numbers")]
+	EmptyInput,
+	#[error("Reached max attempts to re-generate the ID ({attempts} attempts, all rejected by the custom matcher)")]
+	MatcherMaxAttempts { attempts: usize },
+	#[error("ID is rejected by the blocklist")]
+	BlockedId,
+	#[error("ID has more than {capacity} numbers")]
+	CapacityExceeded { capacity: usize },
+	#[error("Invalid blocklist pattern {pattern:?}: {reason}")]
+	InvalidPattern { pattern: String, reason: String },
+	#[error("Character {character:?} at position {position} is not part of the alphabet")]
+	InvalidCharacter { character: char, position: usize },
+	#[error("Separator {0:?} can appear in IDs")]
+	InvalidSeparator(char),
 }
 
 pub type Result<T> = result::Result<T, Error>;
 
+/// The set used for alphabet lookups; the `fast-hash` feature swaps SipHash for FxHash, which
+/// is faster on `char` keys but not resistant to hash flooding.
+#[cfg(feature = "fast-hash")]
+type CharSet = rustc_hash::FxHashSet<char>;
+#[cfg(not(feature = "fast-hash"))]
+type CharSet = HashSet<char>;
+
+/// The set used for the word lookups of the compiled blocklist, swapped like [`CharSet`].
+#[cfg(all(feature = "blocklist", feature = "fast-hash"))]
+type WordSet = rustc_hash::FxHashSet<String>;
+#[cfg(all(feature = "blocklist", not(feature = "fast-hash")))]
+type WordSet = HashSet<String>;
+
+/// The revision of the [Sqids spec](https://github.com/sqids/sqids-spec) this crate implements:
+/// the algorithm fine-tuned in [spec issue #11](https://github.com/sqids/sqids-spec/issues/11),
+/// released by the official ports as 0.3.0. IDs only change along with this constant.
+pub const SPEC_VERSION: &str = "0.3.0";
+
+/// Embeds the bundled blocklist for a language: the word list itself with the
+/// `uncompressed-blocklist` feature, otherwise its packed form, see [`bundled_words`].
+// unused when no `blocklist-<language>` feature is enabled
+#[cfg(all(feature = "blocklist", not(feature = "uncompressed-blocklist")))]
+#[allow(unused_macros)]
+macro_rules! bundled {
+	($language:literal) => {
+		($language, include_bytes!(concat!("blocklist/", $language, ".bin")).as_slice())
+	};
+}
+#[cfg(all(feature = "blocklist", feature = "uncompressed-blocklist"))]
+#[allow(unused_macros)]
+macro_rules! bundled {
+	($language:literal) => {
+		($language, include_bytes!(concat!("blocklist/", $language, ".txt")).as_slice())
+	};
+}
+
+/// The bundled blocklists by language, each behind a `blocklist-<language>` feature.
+#[cfg(feature = "blocklist")]
+const BLOCKLISTS: &[(&str, &[u8])] = &[
+	#[cfg(feature = "blocklist-de")]
+	bundled!("de"),
+	#[cfg(feature = "blocklist-en")]
+	bundled!("en"),
+	#[cfg(feature = "blocklist-es")]
+	bundled!("es"),
+	#[cfg(feature = "blocklist-fr")]
+	bundled!("fr"),
+	#[cfg(feature = "blocklist-hi")]
+	bundled!("hi"),
+	#[cfg(feature = "blocklist-it")]
+	bundled!("it"),
+	#[cfg(feature = "blocklist-pt")]
+	bundled!("pt"),
+];
+
+/// The words of a packed blocklist. The word lists in `src/blocklist/*.txt` are sorted, so each
+/// word is stored as one byte holding the length of the prefix it shares with the previous word
+/// (high nibble) and the length of the rest (low nibble), followed by the rest. This takes the
+/// lists from 4654 to 2717 bytes; `examples/pack_blocklist.rs` writes the `.bin` files.
+#[cfg(all(feature = "blocklist", not(feature = "uncompressed-blocklist")))]
+fn bundled_words(packed: &[u8]) -> Vec<String> {
+	let mut words: Vec<String> = Vec::new();
+	let mut rest = packed;
+	while let Some((&header, tail)) = rest.split_first() {
+		let (shared, len) = ((header >> 4) as usize, (header & 0x0f) as usize);
+		let (suffix, tail) = tail.split_at(len);
+		let mut word = words.last().map_or_else(String::new, |prev| prev[..shared].to_string());
+		word.extend(suffix.iter().map(|&b| b as char));
+		words.push(word);
+		rest = tail;
+	}
+	words
+}
+
+/// The words of a blocklist embedded as is, one per line.
+#[cfg(all(feature = "blocklist", feature = "uncompressed-blocklist"))]
+fn bundled_words(text: &[u8]) -> Vec<String> {
+	String::from_utf8_lossy(text).lines().map(str::to_string).collect()
+}
+
+/// The union of the bundled blocklists whose features are enabled; all of them with the default
+/// `default-blocklist` feature, which is the spec's blocklist. Empty if none are, and then no
+/// words are compiled in.
+#[cfg(feature = "blocklist")]
 pub fn default_blocklist() -> HashSet<String> {
-	serde_json::from_str(include_str!("blocklist.json")).unwrap()
+	default_blocklist_ref().clone()
+}
+
+/// [`default_blocklist`] without the copy, built on first use.
+#[cfg(feature = "blocklist")]
+pub fn default_blocklist_ref() -> &'static HashSet<String> {
+	static DEFAULT_BLOCKLIST: OnceLock<HashSet<String>> = OnceLock::new();
+	DEFAULT_BLOCKLIST
+		.get_or_init(|| BLOCKLISTS.iter().flat_map(|&(_, words)| bundled_words(words)).collect())
+}
+
+/// The bundled blocklist for `language` (`de`, `en`, `es`, `fr`, `hi`, `it` or `pt`), or `None`
+/// if its `blocklist-<language>` feature isn't enabled.
+#[cfg(feature = "blocklist")]
+pub fn blocklist_for(language: &str) -> Option<HashSet<String>> {
+	let &(_, words) = BLOCKLISTS.iter().find(|&&(name, _)| name == language)?;
+	Some(bundled_words(words).into_iter().collect())
+}
+
+/// Maps the characters [`alphabet::NO_LOOKALIKES`] leaves out to the ones they are usually
+/// mistaken for: `0` and `O` to `o`, `1`, `l` and `I` to `i`. It is rejected by alphabets that
+/// contain any of those five characters, like [`alphabet::DEFAULT`].
+pub fn default_homoglyph_map() -> HashMap<char, char> {
+	HashMap::from([('0', 'o'), ('O', 'o'), ('1', 'i'), ('l', 'i'), ('I', 'i')])
+}
+
+/// The spec's consistent shuffle: the same permutation every port applies to the alphabet once
+/// and again between numbers. Handy for comparing ports step by step.
+pub fn shuffle(alphabet: &str) -> String {
+	let mut chars: Vec<char> = alphabet.chars().collect();
+	shuffle_chars(&mut chars);
+	chars.into_iter().collect()
+}
+
+/// [`shuffle`] on a slice of characters, in place.
+pub fn shuffle_chars(alphabet: &mut [char]) {
+	SpecShuffle.shuffle(alphabet)
+}
+
+/// Writes `num` in base `alphabet.len()`, using `alphabet` as the digits (most significant
+/// first). This is the conversion `encode` uses for each number.
+///
+/// # Panics
+///
+/// If `alphabet` has fewer than 2 characters.
+pub fn to_id(num: u64, alphabet: &[char]) -> String {
+	assert!(alphabet.len() >= 2, "alphabet must have at least 2 characters");
+
+	let mut id = String::new();
+	push_id(num, alphabet, &mut Vec::new(), &mut id);
+	id
+}
+
+/// Appends [`to_id`] to `out`, with `digits` as the buffer for the reversed digits.
+fn push_id(num: u64, alphabet: &[char], digits: &mut Vec<char>, out: &mut String) {
+	digits.clear();
+	let mut result = num;
+	// 13 % 4  = 1, 13 / 4 = 3
+	// 3 % 4 = 3,3 / 4 = 0
+	loop {
+		let idx = (result % alphabet.len() as u64) as usize;
+		digits.push(alphabet[idx]);
+		result /= alphabet.len() as u64;
+
+		if result == 0 {
+			break;
+		}
+	}
+
+	out.extend(digits.iter().rev());
+}
+
+/// The inverse of [`to_id`]. Returns `None` if `id` is empty, contains a character outside of
+/// `alphabet`, or doesn't fit in a `u64`.
+pub fn to_number(id: &str, alphabet: &[char]) -> Option<u64> {
+	if id.is_empty() {
+		return None;
+	}
+
+	let mut result: u64 = 0;
+	// idx = 3,result = 3
+	// idx = 1, result = 13
+	for c in id.chars() {
+		let idx = alphabet.iter().position(|&x| x == c)?;
+		result = result.checked_mul(alphabet.len() as u64)?.checked_add(idx as u64)?;
+	}
+
+	Some(result)
+}
+
+/// Checks that `alphabet` is usable by [`Sqids::new`]: single-byte characters only, at least 3
+/// characters long and no repeated characters.
+pub fn validate_alphabet(alphabet: &str) -> Result<()> {
+	check_alphabet(alphabet, false)
+}
+
+fn check_alphabet(alphabet: &str, allow_multibyte: bool) -> Result<()> {
+	let alphabet: Vec<char> = alphabet.chars().collect();
+
+	if !allow_multibyte && alphabet.iter().any(|c| c.len_utf8() > 1) {
+		return Err(Error::AlphabetMultibyteCharacters);
+	}
+
+	if alphabet.len() < 3 {
+		return Err(Error::AlphabetLength);
+	}
+
+	let unique_chars: CharSet = alphabet.iter().cloned().collect();
+	if unique_chars.len() != alphabet.len() {
+		return Err(Error::AlphabetUniqueCharacters);
+	}
+
+	Ok(())
+}
+
+/// A set of character-set rules for alphabets, combined with `|`:
+///
+/// ```
+/// use sqids::AlphabetPolicy;
+///
+/// let policy = AlphabetPolicy::URL_SAFE | AlphabetPolicy::CASE_INSENSITIVE_SAFE;
+/// assert!(policy.validate("abcdefghijklmnopqrstuvwxyz0123456789").is_ok());
+/// assert!(policy.validate("abcABC").is_err());
+/// ```
+///
+/// With the `serde` feature, it is (de)serialized as its bit mask.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct AlphabetPolicy(u8);
+
+impl AlphabetPolicy {
+	/// Only characters that don't need percent-encoding in URLs: `A-Z`, `a-z`, `0-9`, `-`, `.`,
+	/// `_` and `~` (RFC 3986 unreserved characters).
+	pub const URL_SAFE: AlphabetPolicy = AlphabetPolicy(1);
+	/// Only characters from the POSIX portable filename set: `A-Z`, `a-z`, `0-9`, `.`, `_` and
+	/// `-`.
+	pub const FILENAME_SAFE: AlphabetPolicy = AlphabetPolicy(1 << 1);
+	/// No two characters that only differ by case, so IDs don't collide on case-insensitive
+	/// filesystems or lookups.
+	pub const CASE_INSENSITIVE_SAFE: AlphabetPolicy = AlphabetPolicy(1 << 2);
+
+	pub fn contains(self, other: AlphabetPolicy) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Checks `alphabet` against every rule in this set, returning
+	/// [`Error::AlphabetPolicyViolation`] with the offending characters of the first failing one.
+	pub fn validate(self, alphabet: &str) -> Result<()> {
+		if self.contains(Self::URL_SAFE) {
+			let characters: Vec<char> = alphabet
+				.chars()
+				.filter(|&c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')))
+				.collect();
+			if !characters.is_empty() {
+				return Err(Error::AlphabetPolicyViolation { policy: "URL-safe", characters });
+			}
+		}
+
+		if self.contains(Self::FILENAME_SAFE) {
+			let characters: Vec<char> = alphabet
+				.chars()
+				.filter(|&c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+				.collect();
+			if !characters.is_empty() {
+				return Err(Error::AlphabetPolicyViolation { policy: "filename-safe", characters });
+			}
+		}
+
+		if self.contains(Self::CASE_INSENSITIVE_SAFE) {
+			let characters: Vec<char> = alphabet
+				.chars()
+				.filter(|&c| {
+					let other = if c.is_ascii_lowercase() {
+						c.to_ascii_uppercase()
+					} else {
+						c.to_ascii_lowercase()
+					};
+					other != c && alphabet.contains(other)
+				})
+				.collect();
+			if !characters.is_empty() {
+				return Err(Error::AlphabetPolicyViolation {
+					policy: "case-insensitive-safe",
+					characters,
+				});
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl std::ops::BitOr for AlphabetPolicy {
+	type Output = AlphabetPolicy;
+
+	fn bitor(self, rhs: AlphabetPolicy) -> AlphabetPolicy {
+		AlphabetPolicy(self.0 | rhs.0)
+	}
+}
+
+/// What fills an ID up to `min_length`, after a separator following the last number.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PaddingStrategy {
+	/// Slices of the repeatedly shuffled alphabet, as the spec does.
+	#[default]
+	SpecDefault,
+	/// The same character over and over, e.g. `-`. It must be ASCII and not in the alphabet,
+	/// otherwise `Sqids::new` returns [`Error::InvalidPaddingChar`].
+	RepeatChar(char),
+}
+
+/// How IDs are matched against the blocklist. IDs and words are compared lowercased, unless
+/// [`Options::blocklist_case_sensitive`] is set. Changing
+/// the mode changes which IDs are re-generated, and so the IDs of every number whose first
+/// candidate is blocked in one mode but not the other.
+#[cfg(feature = "blocklist")]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BlocklistMode {
+	/// Nothing is blocked, as if the blocklist were empty.
+	Off,
+	/// Only IDs equal to a word are blocked.
+	Exact,
+	/// IDs starting or ending with a word are blocked, and IDs equal to one.
+	PrefixSuffix,
+	/// The spec's rules: IDs or words of up to 3 characters must be equal, words with digits must
+	/// be a prefix or suffix, and other words may appear anywhere in the ID.
+	#[default]
+	Substring,
+}
+
+/// The blocklist words [`Sqids::new_with_report`] dropped, as they were passed in and in
+/// ascending order.
+#[cfg(feature = "blocklist")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlocklistReport {
+	/// How many words were passed in.
+	pub total: usize,
+	/// Words shorter than 3 characters.
+	pub too_short: Vec<String>,
+	/// Words with characters outside the alphabet (and the targets of
+	/// [`Options::blocklist_substitutions`]).
+	pub outside_alphabet: Vec<String>,
+}
+
+#[cfg(feature = "blocklist")]
+impl BlocklistReport {
+	pub fn dropped(&self) -> usize {
+		self.too_short.len() + self.outside_alphabet.len()
+	}
+
+	/// The share of words that were dropped, from 0 to 1; 0 for an empty blocklist.
+	pub fn dropped_fraction(&self) -> f64 {
+		if self.total == 0 {
+			return 0.0;
+		}
+		self.dropped() as f64 / self.total as f64
+	}
+}
+
+/// Why the constructor drops a blocklist word.
+#[cfg(feature = "blocklist")]
+enum DroppedWord {
+	TooShort,
+	OutsideAlphabet,
+}
+
+/// Permutes the alphabet: once when `Sqids` is built, between every two numbers of an ID and
+/// while padding. [`SpecShuffle`] is the spec's shuffle, used unless [`Options::permutation`]
+/// is set.
+///
+/// **Warning:** any other permutation produces IDs that don't follow the spec. No other Sqids
+/// implementation, and no configuration with a different permutation, can decode them.
+///
+/// `shuffle` must be deterministic and must only reorder the characters, otherwise `Sqids::new`
+/// returns [`Error::InvalidPermutation`] (or decoding breaks, if it's only sometimes wrong).
+pub trait AlphabetPermutation: Send + Sync {
+	fn shuffle(&self, alphabet: &mut [char]);
+}
+
+/// The shuffle of the spec.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpecShuffle;
+
+impl AlphabetPermutation for SpecShuffle {
+	fn shuffle(&self, chars: &mut [char]) {
+		// u64 can't overflow for any alphabet of distinct chars, and matches the reference
+		// implementation, whose JavaScript numbers are exact up to 2^53
+		for i in 0..chars.len().saturating_sub(1) {
+			// 0 到 n-1
+			let j = chars.len() - 1 - i; // 反向取vec中对应位置 i = 1 j = n-2
+			let r = (i as u64 * j as u64 + chars[i] as u64 + chars[j] as u64) % chars.len() as u64; //计算出一个新的位置
+			chars.swap(i, r as usize); //将chars[i]换成chars[r]的位置进行互换
+		}
+	}
+}
+
+/// A shared [`AlphabetPermutation`] for [`Options::permutation`]. Two are equal only if they
+/// are the same instance.
+#[derive(Clone)]
+pub struct Permutation(pub Arc<dyn AlphabetPermutation>);
+
+impl Permutation {
+	pub fn new(permutation: impl AlphabetPermutation + 'static) -> Self {
+		Permutation(Arc::new(permutation))
+	}
+
+	fn apply(permutation: &Option<Permutation>, alphabet: &mut [char]) {
+		match permutation {
+			Some(permutation) => permutation.0.shuffle(alphabet),
+			None => SpecShuffle.shuffle(alphabet),
+		}
+	}
+}
+
+impl fmt::Debug for Permutation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("Permutation(..)")
+	}
+}
+
+impl PartialEq for Permutation {
+	fn eq(&self, other: &Self) -> bool {
+		std::ptr::addr_eq(Arc::as_ptr(&self.0), Arc::as_ptr(&other.0))
+	}
+}
+
+impl Eq for Permutation {}
+
+/// Decides which candidate IDs `encode` rejects and re-generates, in place of the blocklist when
+/// set as [`Options::matcher`], e.g. to consult an external profanity filter. It sees every
+/// candidate in full, including a `prefix` but before grouping.
+///
+/// A matcher that rejects too many IDs makes `encode` fail with [`Error::MatcherMaxAttempts`]
+/// once every increment has been tried.
+pub trait BlockedIdMatcher: Send + Sync {
+	fn is_blocked(&self, id: &str) -> bool;
+}
+
+/// The built-in blocklist rules of this `Sqids`, ignoring its own [`Options::matcher`], so a
+/// matcher can add its checks on top of a configuration's blocklist.
+#[cfg(feature = "blocklist")]
+impl BlockedIdMatcher for Sqids {
+	fn is_blocked(&self, id: &str) -> bool {
+		self.blocked_word_in(id).is_some()
+	}
+}
+
+/// A shared [`BlockedIdMatcher`] for [`Options::matcher`]. Two are equal only if they are the
+/// same instance.
+#[derive(Clone)]
+pub struct Matcher(pub Arc<dyn BlockedIdMatcher>);
+
+impl Matcher {
+	pub fn new(matcher: impl BlockedIdMatcher + 'static) -> Self {
+		Matcher(Arc::new(matcher))
+	}
+}
+
+impl fmt::Debug for Matcher {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("Matcher(..)")
+	}
 }
 
-#[derive(Debug)]
+impl PartialEq for Matcher {
+	fn eq(&self, other: &Self) -> bool {
+		std::ptr::addr_eq(Arc::as_ptr(&self.0), Arc::as_ptr(&other.0))
+	}
+}
+
+impl Eq for Matcher {}
+
+fn env_var(name: &str) -> Result<Option<String>> {
+	match env::var(name) {
+		Ok(value) => Ok(Some(value)),
+		Err(env::VarError::NotPresent) => Ok(None),
+		Err(env::VarError::NotUnicode(value)) => Err(Error::EnvVar {
+			name: name.to_string(),
+			value: value.to_string_lossy().into_owned(),
+		}),
+	}
+}
+
+/// Configuration for [`Sqids::new`].
+///
+/// With the `serde` feature enabled, `Options` can be (de)serialized; missing fields fall back to
+/// [`Options::default()`], unknown fields are rejected and the blocklist is a list of strings.
+/// The `config` feature adds loaders for TOML and JSON files.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default, deny_unknown_fields))]
 pub struct Options {
 	pub alphabet: String,
 	pub min_length: u8,
+	/// Words IDs must not contain, only with the `blocklist` feature (on by default). Defaults to
+	/// [`default_blocklist`].
+	#[cfg(feature = "blocklist")]
 	pub blocklist: HashSet<String>,
+	/// Words added to `blocklist` by `Sqids::new`, to block some words on top of the default
+	/// blocklist without repeating it.
+	#[cfg(feature = "blocklist")]
+	pub extra_blocklist: HashSet<String>,
+	/// How IDs are matched against `blocklist`, see [`BlocklistMode`].
+	#[cfg(feature = "blocklist")]
+	pub blocklist_mode: BlocklistMode,
+	/// Match the blocklist with the exact case of its words, e.g. to only block a brand name
+	/// spelled the way the brand spells it. Words are then kept as they are and dropped if a
+	/// character isn't in the alphabet as is, instead of lowercased.
+	#[cfg(feature = "blocklist")]
+	pub blocklist_case_sensitive: bool,
+	/// Characters replaced in candidate IDs (after lowercasing) for a second blocklist check, to
+	/// catch leetspeak like `5h1t` with `5 -> s` and `1 -> i`. The second check uses the same
+	/// [`BlocklistMode`], and words are kept if their characters are in the alphabet or targets
+	/// of a substitution. Decoding is not affected.
+	#[cfg(feature = "blocklist")]
+	pub blocklist_substitutions: Option<HashMap<char, char>>,
+	/// Exceptions to the blocklist, which win over it: a listed blocklist word never blocks an
+	/// ID, and a candidate ID equal to an entry is never blocked, not even by
+	/// [`Options::blocklist_patterns`]. Entries are lowercased like the blocklist.
+	#[cfg(feature = "blocklist")]
+	pub allowlist: HashSet<String>,
+	/// Makes [`Sqids::try_decode`] and [`Sqids::decode_one`] fail with [`Error::BlockedId`] for
+	/// IDs `encode` would have rejected, like crafted IDs spelling a blocked word. The ID is checked
+	/// with its prefix and without group joiners, by the same rules as `encode`, including
+	/// [`Options::matcher`].
+	#[cfg(feature = "blocklist")]
+	pub validate_blocklist_on_decode: bool,
+	/// Regular expressions IDs must not match, with the `regex` feature, e.g. `[0-9]{3}$` for IDs
+	/// ending in three digits. They are matched against the ID lowercased like words are,
+	/// anywhere unless anchored and regardless of [`BlocklistMode`] (except `Off`). Invalid
+	/// patterns make `Sqids::new` return [`Error::InvalidPattern`].
+	#[cfg(feature = "regex")]
+	pub blocklist_patterns: Vec<String>,
+	/// Upper bound on the length of generated IDs. `encode` returns
+	/// [`Error::MaxLengthExceeded`] instead of producing a longer ID, and `Sqids::new` returns
+	/// [`Error::MaxLengthBelowMinLength`] if it is smaller than `min_length`.
+	pub max_length: Option<u16>,
+	/// Pads every ID to exactly this many characters, like `min_length` but also when the
+	/// natural ID is shorter than `min_length` would require, so all IDs have the same length
+	/// whatever the numbers. `encode` returns [`Error::MaxLengthExceeded`] for numbers whose ID
+	/// doesn't fit, and `Sqids::new` returns [`Error::FixedLengthOutOfRange`] if it is below
+	/// `min_length` or above `max_length`. Counts like `max_length`.
+	pub fixed_length: Option<u16>,
+	/// Decode every generated ID and check that it gives back the encoded numbers, returning
+	/// [`Error::RoundtripFailed`] otherwise. A safety net for exotic alphabets; off by default.
+	pub verify_roundtrip: bool,
+	/// Character-set rules the alphabet must follow, checked by `Sqids::new`.
+	pub alphabet_policy: Option<AlphabetPolicy>,
+	/// Cyclically rotates the alphabet left by this many positions before it is shuffled, to derive
+	/// distinct ID spaces from one alphabet. Offsets wrap around the alphabet length.
+	pub alphabet_offset: usize,
+	/// Append a check character computed from the numbers, so `decode` can detect mistyped IDs
+	/// and return an empty `Vec` for them. It is written right after the last number, before any
+	/// `min_length` padding, and counts towards the ID length. This is typo detection, not
+	/// tamper-proofing.
+	pub checksum: bool,
+	/// How many times `encode` re-generates an ID that hits the blocklist before giving up with
+	/// [`Error::BlocklistMaxAttempts`]. Defaults to the alphabet length; since each attempt shifts
+	/// the alphabet offset by one, higher values only repeat earlier candidates. Every attempt
+	/// costs a full encode and blocklist check, so lower values fail faster with aggressive
+	/// blocklists.
+	pub max_regen_attempts: Option<usize>,
+	/// Permutes the alphabet with this seed before the spec shuffle, so applications sharing an
+	/// alphabet get unrelated IDs. A given seed always produces the same IDs; `Some(0)` is a seed
+	/// like any other and differs from `None`.
+	pub seed: Option<u64>,
+	/// Prepended verbatim to every ID, like `usr_` in `usr_Uk6TFd`. Its last character must not be
+	/// in the alphabet, so it can be told apart from the ID; `min_length` and `max_length` only count
+	/// the part after it.
+	pub prefix: Option<String>,
+	/// Decode IDs typed in the wrong case, by folding every letter to the case it has in the
+	/// alphabet. Only ASCII letters are folded, and no two of them may differ only by case,
+	/// otherwise `Sqids::new` returns [`Error::AlphabetCaseConflict`].
+	pub case_insensitive_decode: bool,
+	/// How IDs are padded to `min_length`.
+	pub padding: PaddingStrategy,
+	/// Splits IDs into groups of this many characters joined by the character, like `X7KP-29QD-MMWZ`
+	/// for `(4, '-')`. `decode` ignores the joiner anywhere in the ID. The blocklist, `min_length` and
+	/// `max_length` apply to the ID before grouping; the joiner must not be in the alphabet, otherwise
+	/// `Sqids::new` returns [`Error::InvalidGroup`].
+	pub group: Option<(usize, char)>,
+	/// Characters replaced before decoding, for IDs that went through OCR or a font that confuses
+	/// `0` and `O`. Sources must not be in the alphabet and targets must be, otherwise `Sqids::new`
+	/// returns [`Error::InvalidHomoglyph`]. See [`default_homoglyph_map`].
+	pub homoglyph_map: Option<HashMap<char, char>>,
+	/// Allows alphabets with multibyte characters like Cyrillic or emoji, which the spec rejects
+	/// with [`Error::AlphabetMultibyteCharacters`]. `min_length`, `max_length` and
+	/// [`Sqids::estimate_length`] count characters, not bytes.
+	pub allow_multibyte: bool,
+	/// Replaces the spec shuffle that permutes the alphabet, see [`AlphabetPermutation`]. Not part
+	/// of [`Sqids::config_fingerprint`], and skipped by `serde`.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub permutation: Option<Permutation>,
+	/// Replaces the blocklist check of `encode` (and [`Sqids::is_blocked`]), see
+	/// [`BlockedIdMatcher`]. The blocklist options are then unused. Not part of
+	/// [`Sqids::config_fingerprint`], and skipped by `serde`.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub matcher: Option<Matcher>,
+	/// Whether `encode(&[])` returns an empty ID (the default) or [`Error::EmptyInput`].
+	pub allow_empty: bool,
+	/// Decode IDs with leading or trailing ASCII whitespace, like IDs pasted from logs, by
+	/// trimming it first. Whitespace in the alphabet is kept, at the start also whitespace in
+	/// the prefix, and
+	/// [`Sqids::decode_canonical`] still only accepts the exact ID.
+	pub trim_input: bool,
+	/// Joins the prefixes of [`Sqids::encode_prefixed`] to the IDs, `_` if `None`. It must not be
+	/// in the alphabet, otherwise `Sqids::new` returns [`Error::InvalidSeparator`].
+	pub prefix_separator: Option<char>,
+	/// How many times the alphabet is shuffled between two numbers and while padding, 1 as the
+	/// spec does. More rounds make the IDs of related numbers look less alike, at the cost of
+	/// slower encoding and decoding.
+	///
+	/// **Warning:** any other value produces IDs that don't follow the spec, which no other Sqids
+	/// implementation can decode. 0 is rejected with [`Error::InvalidShuffleRounds`].
+	pub shuffle_rounds: u8,
+	/// IDs `encode` never returns, compared exactly and case-sensitively with the ID including
+	/// the prefix but before grouping, e.g. `ok` for a static route. Unlike the blocklist there is
+	/// no length floor, and they also apply with a matcher or without the `blocklist` feature.
+	/// Exhausting the attempts on one fails with [`Error::BlocklistMaxAttempts`] naming it.
+	pub reserved_ids: HashSet<String>,
 }
 
 impl Options {
+	/// Without the `blocklist` feature, `blocklist` is ignored.
 	pub fn new(
 		alphabet: Option<String>,
 		min_length: Option<u8>,
@@ -41,29 +769,375 @@ impl Options {
 		if let Some(min_length) = min_length {
 			options.min_length = min_length;
 		}
+		#[cfg(feature = "blocklist")]
 		if let Some(blocklist) = blocklist {
 			options.blocklist = blocklist;
 		}
+		#[cfg(not(feature = "blocklist"))]
+		let _ = blocklist;
 
 		options
 	}
+
+	/// Reads options from the `SQIDS_ALPHABET`, `SQIDS_MIN_LENGTH` and `SQIDS_BLOCKLIST`
+	/// environment variables, see [`Options::from_env_with_prefix`].
+	pub fn from_env() -> Result<Self> {
+		Self::from_env_with_prefix("SQIDS_")
+	}
+
+	/// Reads options from the `{prefix}ALPHABET`, `{prefix}MIN_LENGTH` and `{prefix}BLOCKLIST`
+	/// environment variables, using the defaults for unset ones.
+	///
+	/// The blocklist is either a comma-separated list of words, or `@` followed by the path of a
+	/// file read with [`Options::blocklist_from_path`]. An empty value means no blocklist.
+	/// Unparseable values return
+	/// [`Error::EnvVar`] and unreadable files [`Error::BlocklistFile`].
+	pub fn from_env_with_prefix(prefix: &str) -> Result<Self> {
+		let mut options = Options::default();
+
+		if let Some(alphabet) = env_var(&format!("{prefix}ALPHABET"))? {
+			options.alphabet = alphabet;
+		}
+
+		let name = format!("{prefix}MIN_LENGTH");
+		if let Some(value) = env_var(&name)? {
+			options.min_length =
+				value.trim().parse().map_err(|_| Error::EnvVar { name, value: value.clone() })?;
+		}
+
+		#[cfg(feature = "blocklist")]
+		if let Some(value) = env_var(&format!("{prefix}BLOCKLIST"))? {
+			options.blocklist = match value.strip_prefix('@') {
+				Some(path) => Self::blocklist_from_path(path)?,
+				None => value
+					.split(',')
+					.map(str::trim)
+					.filter(|word| !word.is_empty())
+					.map(str::to_string)
+					.collect(),
+			};
+		}
+
+		Ok(options)
+	}
+
+	/// Reads a blocklist with one word per line from a file, like
+	/// [`Options::blocklist_from_reader`]. Errors are [`Error::BlocklistFile`], naming the line
+	/// for invalid UTF-8.
+	#[cfg(feature = "blocklist")]
+	pub fn blocklist_from_path(path: impl AsRef<Path>) -> Result<HashSet<String>> {
+		let path = path.as_ref();
+		let error =
+			|reason: String| Error::BlocklistFile { path: path.display().to_string(), reason };
+
+		let file = fs::File::open(path).map_err(|err| error(err.to_string()))?;
+		Self::blocklist_from_reader(io::BufReader::new(file)).map_err(|err| match err {
+			Error::BlocklistLine { line, reason } => error(format!("line {line}: {reason}")),
+			err => err,
+		})
+	}
+
+	/// Reads a blocklist with one word per line. Words are trimmed, and blank lines and lines
+	/// starting with `#` are ignored. Fails with [`Error::BlocklistLine`] (numbered from 1) if a
+	/// line isn't valid UTF-8 or can't be read.
+	#[cfg(feature = "blocklist")]
+	pub fn blocklist_from_reader<R: BufRead>(mut reader: R) -> Result<HashSet<String>> {
+		let mut blocklist = HashSet::new();
+		let mut bytes = Vec::new();
+
+		for line in 1.. {
+			bytes.clear();
+			let read = reader.read_until(b'\n', &mut bytes);
+			let error = |reason: String| Error::BlocklistLine { line, reason };
+			if read.map_err(|err| error(err.to_string()))? == 0 {
+				break;
+			}
+
+			let word = std::str::from_utf8(&bytes).map_err(|err| error(err.to_string()))?.trim();
+			if !word.is_empty() && !word.starts_with('#') {
+				blocklist.insert(word.to_string());
+			}
+		}
+
+		Ok(blocklist)
+	}
+
+	/// Returns the default alphabet without the characters in `exclude`, e.g. to drop vowels or
+	/// lookalikes. Fails with [`Error::AlphabetExcludedCharacters`] if `exclude` contains a
+	/// character that isn't in the default alphabet, and [`Error::AlphabetLength`] if fewer than 3
+	/// characters would remain.
+	pub fn default_alphabet_without(exclude: &str) -> Result<String> {
+		Self::alphabet_excluding(alphabet::DEFAULT, exclude)
+	}
+
+	/// Returns `base` without the characters in `exclude`, failing like
+	/// [`Options::default_alphabet_without`] and with the errors of [`validate_alphabet`].
+	pub fn alphabet_excluding(base: &str, exclude: &str) -> Result<String> {
+		if !exclude.chars().all(|c| base.contains(c)) {
+			return Err(Error::AlphabetExcludedCharacters);
+		}
+
+		let alphabet: String = base.chars().filter(|&c| !exclude.contains(c)).collect();
+		validate_alphabet(&alphabet)?;
+
+		Ok(alphabet)
+	}
+}
+
+impl fmt::Debug for Options {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut d = f.debug_struct("Options");
+		d.field("alphabet", &self.alphabet).field("min_length", &self.min_length);
+		// the default blocklist is hundreds of words; only its size is useful in logs
+		#[cfg(feature = "blocklist")]
+		d.field("blocklist", &format_args!("<{} words>", self.blocklist.len()))
+			.field("extra_blocklist", &self.extra_blocklist);
+		#[cfg(feature = "blocklist")]
+		d.field("blocklist_mode", &self.blocklist_mode)
+			.field("blocklist_case_sensitive", &self.blocklist_case_sensitive)
+			.field("blocklist_substitutions", &self.blocklist_substitutions)
+			.field("allowlist", &self.allowlist)
+			.field("validate_blocklist_on_decode", &self.validate_blocklist_on_decode);
+		#[cfg(feature = "regex")]
+		d.field("blocklist_patterns", &self.blocklist_patterns);
+		d.field("max_length", &self.max_length)
+			.field("fixed_length", &self.fixed_length)
+			.field("verify_roundtrip", &self.verify_roundtrip)
+			.field("alphabet_policy", &self.alphabet_policy)
+			.field("alphabet_offset", &self.alphabet_offset)
+			.field("checksum", &self.checksum)
+			.field("max_regen_attempts", &self.max_regen_attempts)
+			.field("seed", &self.seed)
+			.field("prefix", &self.prefix)
+			.field("case_insensitive_decode", &self.case_insensitive_decode)
+			.field("padding", &self.padding)
+			.field("group", &self.group)
+			.field("homoglyph_map", &self.homoglyph_map)
+			.field("allow_multibyte", &self.allow_multibyte)
+			.field("permutation", &self.permutation)
+			.field("matcher", &self.matcher)
+			.field("allow_empty", &self.allow_empty)
+			.field("trim_input", &self.trim_input)
+			.field("prefix_separator", &self.prefix_separator)
+			.field("shuffle_rounds", &self.shuffle_rounds)
+			.field("reserved_ids", &self.reserved_ids)
+			.finish()
+	}
 }
 
 impl Default for Options {
 	fn default() -> Self {
 		Options {
-			alphabet: "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789".to_string(),
+			alphabet: alphabet::DEFAULT.to_string(),
 			min_length: 0,
+			#[cfg(feature = "blocklist")]
 			blocklist: default_blocklist(),
+			#[cfg(feature = "blocklist")]
+			extra_blocklist: HashSet::new(),
+			#[cfg(feature = "blocklist")]
+			blocklist_mode: BlocklistMode::Substring,
+			#[cfg(feature = "blocklist")]
+			blocklist_case_sensitive: false,
+			#[cfg(feature = "blocklist")]
+			blocklist_substitutions: None,
+			#[cfg(feature = "blocklist")]
+			allowlist: HashSet::new(),
+			#[cfg(feature = "blocklist")]
+			validate_blocklist_on_decode: false,
+			#[cfg(feature = "regex")]
+			blocklist_patterns: Vec::new(),
+			max_length: None,
+			fixed_length: None,
+			verify_roundtrip: false,
+			alphabet_policy: None,
+			alphabet_offset: 0,
+			checksum: false,
+			max_regen_attempts: None,
+			seed: None,
+			prefix: None,
+			case_insensitive_decode: false,
+			padding: PaddingStrategy::SpecDefault,
+			group: None,
+			homoglyph_map: None,
+			allow_multibyte: false,
+			permutation: None,
+			matcher: None,
+			allow_empty: true,
+			trim_input: false,
+			prefix_separator: None,
+			shuffle_rounds: 1,
+			reserved_ids: HashSet::new(),
 		}
 	}
 }
 
-#[derive(Debug)]
+/// Working memory for [`Sqids::encode_with_scratch`]. Once its buffers have grown to the size
+/// of the IDs being generated, encoding with it no longer allocates.
+#[derive(Clone, Debug, Default)]
+pub struct EncodeScratch {
+	alphabet: Vec<char>,
+	digits: Vec<char>,
+	lowercase: String,
+	substituted: String,
+	grouped: String,
+	id: String,
+}
+
+impl EncodeScratch {
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+/// Alphabets up to this many characters get the first shuffle of every rotation precomputed,
+/// which takes 64 KiB at most.
+const ROTATIONS_MAX_ALPHABET: usize = 128;
+
+#[derive(Clone)]
 pub struct Sqids {
+	original_alphabet: String,
 	alphabet: Vec<char>,
+	// see `Sqids::precompute_rotations`
+	rotations: Option<Arc<[char]>>,
 	min_length: u8,
-	blocklist: HashSet<String>,
+	#[cfg(feature = "blocklist")]
+	blocklist: Arc<HashSet<String>>,
+	// `blocklist` without the allowlisted words, compiled for `blocklist_mode`
+	#[cfg(feature = "blocklist")]
+	blocklist_index: Arc<BlocklistIndex>,
+	#[cfg(feature = "blocklist")]
+	blocklist_mode: BlocklistMode,
+	#[cfg(feature = "blocklist")]
+	blocklist_case_sensitive: bool,
+	#[cfg(feature = "blocklist")]
+	blocklist_substitutions: Option<HashMap<char, char>>,
+	#[cfg(feature = "blocklist")]
+	allowlist: HashSet<String>,
+	#[cfg(feature = "blocklist")]
+	validate_blocklist_on_decode: bool,
+	#[cfg(feature = "regex")]
+	blocklist_patterns: regex::RegexSet,
+	max_length: Option<u16>,
+	fixed_length: Option<u16>,
+	verify_roundtrip: bool,
+	alphabet_policy: Option<AlphabetPolicy>,
+	alphabet_offset: usize,
+	checksum: bool,
+	max_regen_attempts: Option<usize>,
+	seed: Option<u64>,
+	prefix: Option<String>,
+	case_insensitive_decode: bool,
+	padding: PaddingStrategy,
+	group: Option<(usize, char)>,
+	homoglyph_map: Option<HashMap<char, char>>,
+	allow_multibyte: bool,
+	permutation: Option<Permutation>,
+	matcher: Option<Matcher>,
+	allow_empty: bool,
+	trim_input: bool,
+	prefix_separator: Option<char>,
+	shuffle_rounds: u8,
+	reserved_ids: HashSet<String>,
+}
+
+impl fmt::Debug for Sqids {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut d = f.debug_struct("Sqids");
+		d.field("alphabet", &self.original_alphabet).field("min_length", &self.min_length);
+		#[cfg(feature = "blocklist")]
+		d.field("blocklist", &format_args!("<{} words>", self.blocklist.len()));
+		#[cfg(feature = "blocklist")]
+		d.field("blocklist_mode", &self.blocklist_mode)
+			.field("blocklist_case_sensitive", &self.blocklist_case_sensitive)
+			.field("blocklist_substitutions", &self.blocklist_substitutions)
+			.field("allowlist", &self.allowlist)
+			.field("validate_blocklist_on_decode", &self.validate_blocklist_on_decode);
+		#[cfg(feature = "regex")]
+		d.field("blocklist_patterns", &self.blocklist_patterns.patterns());
+		d.field("max_length", &self.max_length)
+			.field("fixed_length", &self.fixed_length)
+			.field("verify_roundtrip", &self.verify_roundtrip)
+			.field("alphabet_policy", &self.alphabet_policy)
+			.field("alphabet_offset", &self.alphabet_offset)
+			.field("checksum", &self.checksum)
+			.field("max_regen_attempts", &self.max_regen_attempts)
+			.field("seed", &self.seed)
+			.field("prefix", &self.prefix)
+			.field("case_insensitive_decode", &self.case_insensitive_decode)
+			.field("padding", &self.padding)
+			.field("group", &self.group)
+			.field("homoglyph_map", &self.homoglyph_map)
+			.field("allow_multibyte", &self.allow_multibyte)
+			.field("permutation", &self.permutation)
+			.field("matcher", &self.matcher)
+			.field("allow_empty", &self.allow_empty)
+			.field("trim_input", &self.trim_input)
+			.field("prefix_separator", &self.prefix_separator)
+			.field("shuffle_rounds", &self.shuffle_rounds)
+			.field("reserved_ids", &self.reserved_ids)
+			.finish()
+	}
+}
+
+/// Two `Sqids` are equal if they were built from equal options, see [`Sqids::options`]; the
+/// permutation and matcher compare by instance.
+impl PartialEq for Sqids {
+	fn eq(&self, other: &Self) -> bool {
+		#[cfg(feature = "blocklist")]
+		let blocklist = self.blocklist == other.blocklist
+			&& self.blocklist_mode == other.blocklist_mode
+			&& self.blocklist_case_sensitive == other.blocklist_case_sensitive
+			&& self.blocklist_substitutions == other.blocklist_substitutions
+			&& self.allowlist == other.allowlist
+			&& self.validate_blocklist_on_decode == other.validate_blocklist_on_decode;
+		#[cfg(not(feature = "blocklist"))]
+		let blocklist = true;
+		#[cfg(feature = "regex")]
+		let patterns = self.blocklist_patterns.patterns() == other.blocklist_patterns.patterns();
+		#[cfg(not(feature = "regex"))]
+		let patterns = true;
+
+		blocklist
+			&& patterns
+			&& self.original_alphabet == other.original_alphabet
+			&& self.alphabet == other.alphabet
+			&& self.min_length == other.min_length
+			&& self.max_length == other.max_length
+			&& self.fixed_length == other.fixed_length
+			&& self.verify_roundtrip == other.verify_roundtrip
+			&& self.alphabet_policy == other.alphabet_policy
+			&& self.alphabet_offset == other.alphabet_offset
+			&& self.checksum == other.checksum
+			&& self.max_regen_attempts == other.max_regen_attempts
+			&& self.seed == other.seed
+			&& self.prefix == other.prefix
+			&& self.case_insensitive_decode == other.case_insensitive_decode
+			&& self.padding == other.padding
+			&& self.group == other.group
+			&& self.homoglyph_map == other.homoglyph_map
+			&& self.allow_multibyte == other.allow_multibyte
+			&& self.permutation == other.permutation
+			&& self.matcher == other.matcher
+			&& self.allow_empty == other.allow_empty
+			&& self.trim_input == other.trim_input
+			&& self.prefix_separator == other.prefix_separator
+			&& self.shuffle_rounds == other.shuffle_rounds
+			&& self.reserved_ids == other.reserved_ids
+	}
+}
+
+impl Eq for Sqids {}
+
+/// Hashes [`Sqids::config_fingerprint`], so configurations can key a `HashMap` and ones that
+/// generate different IDs land in different buckets. Like the fingerprint, hashing is
+/// linearithmic in the blocklist size.
+///
+/// With the `regex` feature, clippy's `mutable_key_type` lint flags such maps because of the
+/// caches inside the compiled patterns, which don't affect the hash or equality.
+impl Hash for Sqids {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.config_fingerprint().hash(state);
+	}
 }
 
 impl Default for Sqids {
@@ -72,220 +1146,1630 @@ impl Default for Sqids {
 	}
 }
 
+/// Same as [`Sqids::new`] with `Some(options)`.
+impl TryFrom<Options> for Sqids {
+	type Error = Error;
+
+	fn try_from(options: Options) -> Result<Self> {
+		Sqids::new(Some(options))
+	}
+}
+
+/// Parses an alphabet into a `Sqids` with the default options otherwise, failing like
+/// [`Sqids::new`] for invalid ones.
+impl FromStr for Sqids {
+	type Err = Error;
+
+	fn from_str(alphabet: &str) -> Result<Self> {
+		Sqids::new(Some(Options::new(Some(alphabet.to_string()), None, None)))
+	}
+}
+
 impl Sqids {
 	pub fn new(options: Option<Options>) -> Result<Self> {
 		let options = options.unwrap_or_default();
-		let alphabet: Vec<char> = options.alphabet.chars().collect();
+		check_alphabet(&options.alphabet, options.allow_multibyte)?;
+		if let Some(policy) = options.alphabet_policy {
+			policy.validate(&options.alphabet)?;
+		}
 
-		for c in alphabet.iter() {
-			if c.len_utf8() > 1 {
-				return Err(Error::AlphabetMultibyteCharacters);
+		if let Some(max_length) = options.max_length {
+			if max_length < options.min_length as u16 {
+				return Err(Error::MaxLengthBelowMinLength);
 			}
 		}
-
-		if alphabet.len() < 3 {
-			return Err(Error::AlphabetLength);
+		if let Some(fixed_length) = options.fixed_length {
+			if fixed_length < options.min_length as u16
+				|| options.max_length.is_some_and(|max_length| max_length < fixed_length)
+			{
+				return Err(Error::FixedLengthOutOfRange);
+			}
 		}
 
-		let unique_chars: HashSet<char> = alphabet.iter().cloned().collect();
-		if unique_chars.len() != alphabet.len() {
-			return Err(Error::AlphabetUniqueCharacters);
+		if options.case_insensitive_decode {
+			let lowercase: CharSet =
+				options.alphabet.chars().filter(char::is_ascii_lowercase).collect();
+			if let Some(c) = options
+				.alphabet
+				.chars()
+				.find(|c| c.is_ascii_uppercase() && lowercase.contains(&c.to_ascii_lowercase()))
+			{
+				return Err(Error::AlphabetCaseConflict(c.to_ascii_lowercase()));
+			}
 		}
 
-		let lowercase_alphabet: Vec<char> =
-			alphabet.iter().map(|c| c.to_ascii_lowercase()).collect();
-		let filtered_blocklist: HashSet<String> = options
-			.blocklist
-			.iter()
-			.filter_map(|word| {
-				let word = word.to_lowercase();
-				if word.len() >= 3 && word.chars().all(|c| lowercase_alphabet.contains(&c)) {
-					Some(word)
-				} else {
-					None
-				}
-			})
-			.collect();
-
-		Ok(Sqids {
-			alphabet: Self::shuffle(&alphabet),
-			min_length: options.min_length,
-			blocklist: filtered_blocklist,
-		})
-	}
-
-	pub fn encode(&self, numbers: &[u64]) -> Result<String> {
-		if numbers.is_empty() {
-			return Ok(String::new());
+		if let PaddingStrategy::RepeatChar(c) = options.padding {
+			if !c.is_ascii() || options.alphabet.contains(c) {
+				return Err(Error::InvalidPaddingChar);
+			}
 		}
 
-		self.encode_numbers(numbers, 0)
-	}
+		if let Some((size, joiner)) = options.group {
+			if size == 0 || options.alphabet.contains(joiner) {
+				return Err(Error::InvalidGroup);
+			}
+		}
 
-	pub fn decode(&self, id: &str) -> Vec<u64> {
-		let mut ret = Vec::new();
+		if let Some(permutation) = &options.permutation {
+			let mut original: Vec<char> = options.alphabet.chars().collect();
+			let mut permuted = original.clone();
+			permutation.0.shuffle(&mut permuted);
+			original.sort_unstable();
+			permuted.sort_unstable();
+			if permuted != original {
+				return Err(Error::InvalidPermutation);
+			}
+		}
 
-		if id.is_empty() {
-			return ret;
+		if let Some(map) = &options.homoglyph_map {
+			for (&from, &to) in map {
+				if options.alphabet.contains(from) || !options.alphabet.contains(to) {
+					return Err(Error::InvalidHomoglyph { from, to });
+				}
+			}
 		}
 
-		let alphabet_chars: HashSet<char> = self.alphabet.iter().cloned().collect(); //字符表，转成set
-		if !id.chars().all(|c| alphabet_chars.contains(&c)) { //如果发现有不存在的字符，就直接返回空数组
-			return ret; 
+		if let Some(prefix) = &options.prefix {
+			match prefix.chars().last() {
+				Some(last) if !options.alphabet.contains(last) => {}
+				_ => return Err(Error::InvalidPrefix),
+			}
 		}
 
-		let prefix = id.chars().next().unwrap(); //取得首字符，确认prefix
-		let offset = self.alphabet.iter().position(|&c| c == prefix).unwrap(); //方向计算对应的offset
-		let mut alphabet: Vec<char> =
-			self.alphabet.iter().cycle().skip(offset).take(self.alphabet.len()).copied().collect();
+		if let Some(sep) = options.prefix_separator {
+			if options.alphabet.contains(sep) {
+				return Err(Error::InvalidSeparator(sep));
+			}
+		}
 
-		alphabet = alphabet.into_iter().rev().collect(); //构建和编码时相同的字符表
+		if options.shuffle_rounds == 0 {
+			return Err(Error::InvalidShuffleRounds);
+		}
 
-		let mut id = id[1..].to_string(); //删除prefix
+		#[cfg(feature = "regex")]
+		for pattern in &options.blocklist_patterns {
+			if let Err(err) = regex::Regex::new(pattern) {
+				return Err(Error::InvalidPattern {
+					pattern: pattern.clone(),
+					reason: err.to_string(),
+				});
+			}
+		}
 
-		while !id.is_empty() {
-			let separator = alphabet[0];
-			let chunks: Vec<&str> = id.split(separator).collect(); //如果存在多个numbers编码后的ID，那么就存在多个chunk
-			if !chunks.is_empty() {
-				if chunks[0].is_empty() {
-					return ret;
-				}
+		#[cfg(feature = "blocklist")]
+		let options = {
+			let chars = Self::blocklist_chars(&options);
+			let case_sensitive = options.blocklist_case_sensitive;
+			let mut blocklist = Self::filter_blocklist(&options.blocklist, &chars, case_sensitive);
+			blocklist.extend(Self::filter_blocklist(
+				&options.extra_blocklist,
+				&chars,
+				case_sensitive,
+			));
+			let allowlist = if case_sensitive {
+				options.allowlist
+			} else {
+				options.allowlist.iter().map(|entry| entry.to_lowercase()).collect()
+			};
+			Options { blocklist, extra_blocklist: HashSet::new(), allowlist, ..options }
+		};
 
-				let alphabet_without_separator: Vec<char> =
-					alphabet.iter().copied().skip(1).collect(); //去掉第一个字符的字符表
-				ret.push(self.to_number(chunks[0], &alphabet_without_separator)); //反转成数字
+		Ok(Self::new_unchecked(options))
+	}
 
-				if chunks.len() > 1 {
-					alphabet = Self::shuffle(&alphabet); //对字符表进行洗牌
+	/// Like [`Sqids::new`], but also returns which blocklist words were dropped, e.g. to notice an
+	/// alphabet that leaves most of the default blocklist unusable.
+	#[cfg(feature = "blocklist")]
+	pub fn new_with_report(options: Option<Options>) -> Result<(Self, BlocklistReport)> {
+		let options = options.unwrap_or_default();
 
-				}
+		let case_sensitive = options.blocklist_case_sensitive;
+		let chars = Self::blocklist_alphabet(&Self::blocklist_chars(&options), case_sensitive);
+		let words: HashSet<&String> =
+			options.blocklist.iter().chain(&options.extra_blocklist).collect();
+		let mut report = BlocklistReport { total: words.len(), ..Default::default() };
+		for word in words {
+			match Self::blocklist_word(word, &chars, case_sensitive) {
+				Ok(_) => {}
+				Err(DroppedWord::TooShort) => report.too_short.push(word.clone()),
+				Err(DroppedWord::OutsideAlphabet) => report.outside_alphabet.push(word.clone()),
 			}
-
-			id = chunks[1..].join(&separator.to_string());
-     //删除第一个chunk，然后用当前separator进行粘合，因为下一轮的separator已经变了
 		}
+		report.too_short.sort();
+		report.outside_alphabet.sort();
 
-		ret
+		Ok((Self::new(Some(options))?, report))
 	}
 
-	fn encode_numbers(&self, numbers: &[u64], increment: usize) -> Result<String> {
-		if increment > self.alphabet.len() { //步进不能大于整个字符表
-			return Err(Error::BlocklistMaxAttempts);
+	/// Builds a `Sqids` without validating the options or filtering the blocklist, for options
+	/// that already went through [`Sqids::new`], e.g. when loading many stored configurations.
+	///
+	/// The blocklist is used as is, so it must already be filtered and lowercased (unless the
+	/// matching is case-sensitive), as returned by [`Sqids::options`]. Invalid options are never
+	/// undefined behavior, but may panic or produce IDs that don't decode.
+	pub fn new_unchecked(options: Options) -> Self {
+		let mut alphabet =
+			Self::prepare_alphabet(&options.alphabet, options.alphabet_offset, options.seed);
+		Permutation::apply(&options.permutation, &mut alphabet);
+		let rotations = Self::precompute_rotations(&alphabet, &options.permutation);
 
+		Sqids {
+			original_alphabet: options.alphabet,
+			alphabet,
+			rotations,
+			min_length: options.min_length,
+			#[cfg(feature = "blocklist")]
+			blocklist_index: Arc::new(BlocklistIndex::new(
+				&options.blocklist,
+				options.blocklist_mode,
+				&options.allowlist,
+			)),
+			#[cfg(feature = "blocklist")]
+			blocklist: Arc::new(options.blocklist),
+			#[cfg(feature = "blocklist")]
+			blocklist_mode: options.blocklist_mode,
+			#[cfg(feature = "blocklist")]
+			blocklist_case_sensitive: options.blocklist_case_sensitive,
+			#[cfg(feature = "blocklist")]
+			blocklist_substitutions: options.blocklist_substitutions,
+			#[cfg(feature = "blocklist")]
+			allowlist: options.allowlist,
+			#[cfg(feature = "blocklist")]
+			validate_blocklist_on_decode: options.validate_blocklist_on_decode,
+			#[cfg(feature = "regex")]
+			blocklist_patterns: regex::RegexSet::new(&options.blocklist_patterns)
+				.expect("invalid blocklist pattern"),
+			max_length: options.max_length,
+			fixed_length: options.fixed_length,
+			verify_roundtrip: options.verify_roundtrip,
+			alphabet_policy: options.alphabet_policy,
+			alphabet_offset: options.alphabet_offset,
+			checksum: options.checksum,
+			max_regen_attempts: options.max_regen_attempts,
+			seed: options.seed,
+			prefix: options.prefix,
+			case_insensitive_decode: options.case_insensitive_decode,
+			padding: options.padding,
+			group: options.group,
+			homoglyph_map: options.homoglyph_map,
+			allow_multibyte: options.allow_multibyte,
+			permutation: options.permutation,
+			matcher: options.matcher,
+			allow_empty: options.allow_empty,
+			trim_input: options.trim_input,
+			prefix_separator: options.prefix_separator,
+			shuffle_rounds: options.shuffle_rounds,
+			reserved_ids: options.reserved_ids,
 		}
-    //将numbers的长度作为初始值
-    // v = numbers[i]
-    // a = a + i + self.alphabet[v % self.alphabet.len()]
-		let mut offset = numbers.iter().enumerate().fold(numbers.len(), |a, (i, &v)| {
-			self.alphabet[v as usize % self.alphabet.len()] as usize + i + a // a + i + alphabet[v % len(alphabet)]
-		}) % self.alphabet.len();
-
-    //计算出最终的offset
-		offset = (offset + increment) % self.alphabet.len();
-    //在offset这个位置将整个alphabet进行前后调换
-		let mut alphabet: Vec<char> =
-			self.alphabet.iter().cycle().skip(offset).take(self.alphabet.len()).copied().collect();
-    //取出字符表第一个字符，作为前缀字符，放在生成的ID的最前面，用来作ID首字符
+	}
 
-		let prefix = alphabet[0];
-    //将整个字符表进行逆转
-		alphabet = alphabet.into_iter().rev().collect();
-    //将prefix变成字符串放入Vec
-		let mut ret: Vec<String> = vec![prefix.to_string()];
-    //开始遍历numbers序列
-		for (i, &num) in numbers.iter().enumerate() {
-			  ret.push(self.to_id(num, &alphabet[1..])); //使用除了第一个字符以外的字符表进行转换
+	/// Builds a `Sqids` whose alphabet is `base_alphabet` (the default one if `None`) permuted by
+	/// `seed`, so services sharing a seed generate the same IDs without sharing an alphabet
+	/// string. The permutation only uses integer arithmetic on the seed's UTF-8 bytes and is the
+	/// same on every platform; an empty seed leaves the base alphabet as is.
+	///
+	/// This is obfuscation, not encryption: the seed is not a secret key and IDs can still be
+	/// decoded by anyone who recovers the permuted alphabet.
+	pub fn from_seed(seed: &str, base_alphabet: Option<&str>) -> Result<Self> {
+		let base_alphabet = base_alphabet.unwrap_or(alphabet::DEFAULT);
+		validate_alphabet(base_alphabet)?;
 
-			if i < numbers.len() - 1 {
-				ret.push(alphabet[0].to_string()); //放入分割符号
-				alphabet = Self::shuffle(&alphabet); //再次洗牌
-			}
-		}
+		let chars: Vec<char> = base_alphabet.chars().collect();
+		let alphabet = Self::keyed_shuffle(&chars, seed.as_bytes()).into_iter().collect();
 
-		let mut id = ret.join(""); //将所有的id进行连接
+		Sqids::new(Some(Options::new(Some(alphabet), None, None)))
+	}
 
-		if self.min_length as usize > id.len() { //需要生成最小字符串大于生成的id长度
-			id += &alphabet[0].to_string(); //继续添加分割符号
+	/// A `Sqids` using the lowercase letters `a-z` and the default blocklist.
+	pub fn lowercase(min_length: Option<u8>) -> Self {
+		Self::preset("abcdefghijklmnopqrstuvwxyz", min_length)
+	}
 
-			while self.min_length as usize - id.len() > 0 {
-				alphabet = Self::shuffle(&alphabet); //洗牌
+	/// A `Sqids` using the uppercase letters `A-Z` and the default blocklist.
+	pub fn uppercase(min_length: Option<u8>) -> Self {
+		Self::preset("ABCDEFGHIJKLMNOPQRSTUVWXYZ", min_length)
+	}
 
-				let slice_len = min(self.min_length as usize - id.len(), alphabet.len());
-				let slice: Vec<char> = alphabet.iter().take(slice_len).cloned().collect();
+	/// A `Sqids` using the digits `0-9`, the uppercase letters `A-Z` and the default blocklist.
+	pub fn digits_and_upper(min_length: Option<u8>) -> Self {
+		Self::preset("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ", min_length)
+	}
 
-				id += &slice.iter().collect::<String>(); //填充垃圾字符串
+	/// Returns a copy with a different `min_length`, reusing the shuffled alphabet and the
+	/// filtered blocklist. Fails with [`Error::MaxLengthBelowMinLength`] if `min_length` is above
+	/// `max_length`, and with [`Error::FixedLengthOutOfRange`] if it is above `fixed_length`.
+	///
+	/// There is no `with_alphabet`: a different alphabet is a different ID space, build a new
+	/// `Sqids` for that.
+	pub fn with_min_length(&self, min_length: u8) -> Result<Sqids> {
+		if let Some(fixed_length) = self.fixed_length {
+			if fixed_length < min_length as u16 {
+				return Err(Error::FixedLengthOutOfRange);
 			}
 		}
-
-		if self.is_blocked_id(&id) { //如果是非法的id，那么就增加步长，重新来一次
-			id = self.encode_numbers(numbers, increment + 1)?;
-		}
-
-		Ok(id)
-	}
-
-	fn to_id(&self, num: u64, alphabet: &[char]) -> String {
-		let mut id = Vec::new();
-		let mut result = num;
-    // 13 % 4  = 1, 13 / 4 = 3
-    // 3 % 4 = 3,3 / 4 = 0
-		loop {
-			let idx = (result % alphabet.len() as u64) as usize;
-			id.insert(0, alphabet[idx]);
-			result /= alphabet.len() as u64;
-
-			if result == 0 {
-				break;
+		if let Some(max_length) = self.max_length {
+			if max_length < min_length as u16 {
+				return Err(Error::MaxLengthBelowMinLength);
 			}
 		}
 
-		id.into_iter().collect()
+		Ok(Sqids { min_length, ..self.clone() })
 	}
 
-	fn to_number(&self, id: &str, alphabet: &[char]) -> u64 {
-		let mut result = 0;
-    // idx = 3,result = 3
-    // idx = 1, result = 13
-		for c in id.chars() {
-			let idx = alphabet.iter().position(|&x| x == c).unwrap();
-			result = result * alphabet.len() as u64 + idx as u64;
-		}
-
-		result
+	/// Returns a copy with a different blocklist, reusing the shuffled alphabet. The words are
+	/// filtered the same way the constructor filters them.
+	#[cfg(feature = "blocklist")]
+	pub fn with_blocklist(&self, blocklist: HashSet<String>) -> Result<Sqids> {
+		let blocklist = self.filter_words(&blocklist);
+		let mut sqids = Sqids { blocklist: Arc::new(blocklist), ..self.clone() };
+		sqids.index_blocklist();
+		Ok(sqids)
 	}
 
-	fn shuffle(alphabet: &[char]) -> Vec<char> {
-		let mut chars: Vec<char> = alphabet.to_vec(); //转化为vec
+	/// Adds `words` to the blocklist, filtered the same way the constructor filters them, and
+	/// returns how many passed the filter.
+	///
+	/// **This only changes which IDs are generated from now on.** Numbers whose ID contains a
+	/// new word get a different ID than before, while IDs issued before still decode, blocked
+	/// or not. Clones of this `Sqids` keep the blocklist they had.
+	#[cfg(feature = "blocklist")]
+	pub fn add_blocklist_words<I: IntoIterator<Item = String>>(&mut self, words: I) -> usize {
+		let words = self.filter_words(&words.into_iter().collect());
+		let count = words.len();
+		Arc::make_mut(&mut self.blocklist).extend(words);
+		self.index_blocklist();
+		count
+	}
 
-		for i in 0..(chars.len() - 1) { // 0 到 n-1
-			let j = chars.len() - 1 - i; // 反向取vec中对应位置 i = 1 j = n-2
-			let r = (i as u32 * j as u32 + chars[i] as u32 + chars[j] as u32) % chars.len() as u32; //计算出一个新的位置
-			chars.swap(i, r as usize); //将chars[i]换成chars[r]的位置进行互换
+	/// Removes `words` from the blocklist (lowercased, unless the matching is case-sensitive)
+	/// and returns how many were in it. Like [`Sqids::add_blocklist_words`], this only changes
+	/// the IDs generated from now on: numbers that were re-generated because of a removed word
+	/// get their first candidate back, which differs from the ID issued before.
+	#[cfg(feature = "blocklist")]
+	pub fn remove_blocklist_words<I: IntoIterator<Item = String>>(&mut self, words: I) -> usize {
+		let case_sensitive = self.blocklist_case_sensitive;
+		let blocklist = Arc::make_mut(&mut self.blocklist);
+		let removed = words
+			.into_iter()
+			.filter(|word| {
+				if case_sensitive {
+					blocklist.remove(word)
+				} else {
+					blocklist.remove(&word.to_lowercase())
+				}
+			})
+			.count();
+		self.index_blocklist();
+		removed
+	}
+
+	#[cfg(feature = "blocklist")]
+	fn index_blocklist(&mut self) {
+		self.blocklist_index =
+			Arc::new(BlocklistIndex::new(&self.blocklist, self.blocklist_mode, &self.allowlist));
+	}
+
+	#[cfg(feature = "blocklist")]
+	fn filter_words(&self, words: &HashSet<String>) -> HashSet<String> {
+		let mut chars = self.alphabet.clone();
+		chars.extend(self.blocklist_substitutions.iter().flat_map(|map| map.values()));
+		Self::filter_blocklist(words, &chars, self.blocklist_case_sensitive)
+	}
+
+	/// The characters blocklist words may consist of.
+	#[cfg(feature = "blocklist")]
+	fn blocklist_chars(options: &Options) -> Vec<char> {
+		let mut chars: Vec<char> = options.alphabet.chars().collect();
+		chars.extend(options.blocklist_substitutions.iter().flat_map(|map| map.values()));
+		chars
+	}
+
+	/// Returns the same configuration with the alphabet permuted by `tenant_key`, sharing the
+	/// blocklist instead of filtering it again. IDs of different tenants decode into unrelated
+	/// numbers rather than each other's; [`Sqids::for_tenant`] makes them fail to decode.
+	///
+	/// On an instance without a `seed` this is the same as setting `seed: Some(tenant_key)`;
+	/// otherwise the seed becomes `seed * 0x9e3779b97f4a7c15 ^ tenant_key` (wrapping), which
+	/// [`Sqids::options`] reports. Both are stable across releases.
+	pub fn derive_for_tenant(&self, tenant_key: u64) -> Sqids {
+		let seed = match self.seed {
+			Some(seed) => seed.wrapping_mul(0x9e3779b97f4a7c15) ^ tenant_key,
+			None => tenant_key,
+		};
+		let mut alphabet =
+			Self::prepare_alphabet(&self.original_alphabet, self.alphabet_offset, Some(seed));
+		Permutation::apply(&self.permutation, &mut alphabet);
+		let rotations = Self::precompute_rotations(&alphabet, &self.permutation);
+
+		Sqids { alphabet, rotations, seed: Some(seed), ..self.clone() }
+	}
+
+	/// [`Sqids::derive_for_tenant`] with `prefix` in place of [`Options::prefix`], for telling
+	/// the IDs of tenants apart. Fails with [`Error::InvalidPrefix`] if the last character of
+	/// `prefix` is one that `decode` reads as part of an ID: in the alphabet, the joiner, the
+	/// padding character, a homoglyph or, with `case_insensitive_decode`, a letter of the
+	/// alphabet in the other case.
+	///
+	/// Tenants derived from the same `Sqids` with different prefixes never accept each other's IDs:
+	/// `try_decode`, `decode_one` and `validate_id` fail and `decode` gives no numbers, because an
+	/// ID either doesn't start with the other prefix or has the last character of its own in the
+	/// part after it. This relies on the prefixes only, not the keys; an ID whose prefix was
+	/// swapped by hand decodes to unrelated numbers, which `checksum` catches most of the time.
+	///
+	/// ```
+	/// # use sqids::{Error, Sqids};
+	/// let base = Sqids::default();
+	/// let acme = base.for_tenant(1, "acme_").unwrap();
+	/// let initech = base.for_tenant(2, "initech_").unwrap();
+	///
+	/// let id = acme.encode(&[1, 2, 3]).unwrap();
+	/// assert!(acme.validate_id(&id).is_ok());
+	/// assert_eq!(initech.try_decode(&id), Err(Error::PrefixMismatch));
+	/// ```
+	pub fn for_tenant(&self, tenant_key: u64, prefix: &str) -> Result<Sqids> {
+		match prefix.chars().last() {
+			Some(last) if !self.reads_as_id(last) => {}
+			_ => return Err(Error::InvalidPrefix),
+		}
+
+		Ok(Sqids { prefix: Some(prefix.to_string()), ..self.derive_for_tenant(tenant_key) })
+	}
+
+	/// Whether `decode` may read `c` as a character of an ID.
+	pub(crate) fn reads_as_id(&self, c: char) -> bool {
+		let in_alphabet = |c: char| self.alphabet.contains(&c);
+		in_alphabet(c)
+			|| self.group.is_some_and(|(_, joiner)| joiner == c)
+			|| self.padding == PaddingStrategy::RepeatChar(c)
+			|| self.homoglyph_map.as_ref().is_some_and(|map| map.contains_key(&c))
+			|| self.case_insensitive_decode
+				&& (in_alphabet(c.to_ascii_lowercase()) || in_alphabet(c.to_ascii_uppercase()))
+	}
+
+	/// For each offset, the alphabet rotated by it, reversed and shuffled once, one after the
+	/// other: the alphabet of the second number of an ID, or of the padding after a single
+	/// number. `None` for alphabets longer than [`ROTATIONS_MAX_ALPHABET`].
+	fn precompute_rotations(
+		alphabet: &[char],
+		permutation: &Option<Permutation>,
+	) -> Option<Arc<[char]>> {
+		if alphabet.len() > ROTATIONS_MAX_ALPHABET {
+			return None;
+		}
+
+		let mut rotations = Vec::with_capacity(alphabet.len() * alphabet.len());
+		let mut rotated = alphabet.to_vec();
+		for _ in 0..alphabet.len() {
+			let start = rotations.len();
+			rotations.extend(rotated.iter().rev());
+			Permutation::apply(permutation, &mut rotations[start..]);
+			rotated.rotate_left(1);
+		}
+
+		Some(rotations.into())
+	}
+
+	/// Shuffles `alphabet` `shuffle_rounds` times. It is the alphabet rotated by `offset`,
+	/// reversed and shuffled `shuffles` times before; the first round of the first shuffle uses
+	/// the precomputed rotations.
+	fn shuffle_next(&self, alphabet: &mut [char], offset: usize, shuffles: usize) {
+		let mut rounds = self.shuffle_rounds;
+		if let (Some(rotations), 0) = (&self.rotations, shuffles) {
+			let len = alphabet.len();
+			alphabet.copy_from_slice(&rotations[offset * len..][..len]);
+			rounds -= 1;
+		}
+
+		for _ in 0..rounds {
+			Permutation::apply(&self.permutation, alphabet);
+		}
+	}
+
+	#[cfg(feature = "blocklist")]
+	fn filter_blocklist(
+		blocklist: &HashSet<String>,
+		alphabet: &[char],
+		case_sensitive: bool,
+	) -> HashSet<String> {
+		let alphabet = Self::blocklist_alphabet(alphabet, case_sensitive);
+		blocklist
+			.iter()
+			.filter_map(|word| Self::blocklist_word(word, &alphabet, case_sensitive).ok())
+			.collect()
+	}
+
+	#[cfg(feature = "blocklist")]
+	fn blocklist_alphabet(alphabet: &[char], case_sensitive: bool) -> Vec<char> {
+		if case_sensitive {
+			alphabet.to_vec()
+		} else {
+			alphabet.iter().map(|c| c.to_ascii_lowercase()).collect()
+		}
+	}
+
+	/// `word` as it is matched, or why it is dropped. `alphabet` comes from
+	/// [`Sqids::blocklist_alphabet`].
+	#[cfg(feature = "blocklist")]
+	fn blocklist_word(
+		word: &str,
+		alphabet: &[char],
+		case_sensitive: bool,
+	) -> std::result::Result<String, DroppedWord> {
+		let word = if case_sensitive { word.to_string() } else { word.to_lowercase() };
+		if word.chars().count() < 3 {
+			Err(DroppedWord::TooShort)
+		} else if !word.chars().all(|c| alphabet.contains(&c)) {
+			Err(DroppedWord::OutsideAlphabet)
+		} else {
+			Ok(word)
+		}
+	}
+
+	fn preset(alphabet: &str, min_length: Option<u8>) -> Self {
+		Sqids::new(Some(Options::new(Some(alphabet.to_string()), min_length, None))).unwrap()
+	}
+
+	/// The alphabet as it was passed in, before shuffling.
+	pub fn alphabet(&self) -> String {
+		self.original_alphabet.clone()
+	}
+
+	pub fn min_length(&self) -> u8 {
+		self.min_length
+	}
+
+	pub fn max_length(&self) -> Option<u16> {
+		self.max_length
+	}
+
+	pub fn fixed_length(&self) -> Option<u16> {
+		self.fixed_length
+	}
+
+	/// The effective blocklist: lowercased (unless the matching is case-sensitive), and without
+	/// the words the constructor dropped for being too short or containing characters outside the
+	/// alphabet.
+	#[cfg(feature = "blocklist")]
+	pub fn blocklist(&self) -> &HashSet<String> {
+		&self.blocklist
+	}
+
+	/// The words of [`Sqids::blocklist`] in ascending order, e.g. for diffing against the input
+	/// list.
+	#[cfg(feature = "blocklist")]
+	pub fn effective_blocklist(&self) -> impl Iterator<Item = &str> {
+		let mut words: Vec<&str> = self.blocklist.iter().map(String::as_str).collect();
+		words.sort_unstable();
+		words.into_iter()
+	}
+
+	/// How many words survived the constructor's filtering, see [`Sqids::blocklist`].
+	#[cfg(feature = "blocklist")]
+	pub fn blocklist_len(&self) -> usize {
+		self.blocklist.len()
+	}
+
+	/// Returns options that build an equivalent `Sqids`. The blocklist is the effective one, see
+	/// [`Sqids::blocklist`].
+	pub fn options(&self) -> Options {
+		Options {
+			alphabet: self.alphabet(),
+			min_length: self.min_length,
+			#[cfg(feature = "blocklist")]
+			blocklist: (*self.blocklist).clone(),
+			#[cfg(feature = "blocklist")]
+			extra_blocklist: HashSet::new(),
+			#[cfg(feature = "blocklist")]
+			blocklist_mode: self.blocklist_mode,
+			#[cfg(feature = "blocklist")]
+			blocklist_case_sensitive: self.blocklist_case_sensitive,
+			#[cfg(feature = "blocklist")]
+			blocklist_substitutions: self.blocklist_substitutions.clone(),
+			#[cfg(feature = "blocklist")]
+			allowlist: self.allowlist.clone(),
+			#[cfg(feature = "blocklist")]
+			validate_blocklist_on_decode: self.validate_blocklist_on_decode,
+			#[cfg(feature = "regex")]
+			blocklist_patterns: self.blocklist_patterns.patterns().to_vec(),
+			max_length: self.max_length,
+			fixed_length: self.fixed_length,
+			verify_roundtrip: self.verify_roundtrip,
+			alphabet_policy: self.alphabet_policy,
+			alphabet_offset: self.alphabet_offset,
+			checksum: self.checksum,
+			max_regen_attempts: self.max_regen_attempts,
+			seed: self.seed,
+			prefix: self.prefix.clone(),
+			case_insensitive_decode: self.case_insensitive_decode,
+			padding: self.padding,
+			group: self.group,
+			homoglyph_map: self.homoglyph_map.clone(),
+			allow_multibyte: self.allow_multibyte,
+			permutation: self.permutation.clone(),
+			matcher: self.matcher.clone(),
+			allow_empty: self.allow_empty,
+			trim_input: self.trim_input,
+			prefix_separator: self.prefix_separator,
+			shuffle_rounds: self.shuffle_rounds,
+			reserved_ids: self.reserved_ids.clone(),
+		}
+	}
+
+	/// A stable 64-bit fingerprint of everything that determines which IDs get generated, for
+	/// checking that several services (or language ports) share a configuration.
+	///
+	/// It is the 64-bit FNV-1a hash of the following bytes:
+	///
+	/// 1. the original alphabet rotated by `alphabet_offset` and permuted by `seed`, followed by a
+	///    `0x00` byte
+	/// 2. `min_length` as a single byte
+	/// 3. each word of the effective (lowercased, filtered) blocklist in ascending byte order, each
+	///    followed by a `0x00` byte (nothing without the `blocklist` feature)
+	/// 4. the prefix, if set
+	/// 5. with [`PaddingStrategy::RepeatChar`], a `0x00` byte and the padding character
+	/// 6. with a [`BlocklistMode`] other than `Substring`, a `0x01` byte and the mode's index
+	///    (0 for `Off`, 1 for `Exact`, 2 for `PrefixSuffix`)
+	/// 7. with [`Options::blocklist_case_sensitive`], a `0x02` byte
+	/// 8. with [`Options::blocklist_substitutions`], a `0x03` byte and each source and target
+	///    character, in ascending order of the sources
+	/// 9. with [`Options::blocklist_patterns`], a `0x04` byte and each pattern in the given order,
+	///    each followed by a `0x00` byte
+	/// 10. with [`Options::prefix_separator`], a `0x05` byte and the separator
+	/// 11. with [`Options::shuffle_rounds`] other than 1, a `0x06` byte and the number of rounds
+	/// 12. with [`Options::allowlist`], a `0x07` byte and each entry in ascending byte order, each
+	///     followed by a `0x00` byte
+	/// 13. with [`Options::reserved_ids`], a `0x08` byte and each ID in ascending byte order, each
+	///     followed by a `0x00` byte
+	/// 14. with [`Options::fixed_length`], a `0x09` byte and the length as two big-endian bytes
+	/// 15. with [`Options::checksum`], a `0x0a` byte
+	/// 16. with [`Options::group`], a `0x0b` byte, the group size as a big-endian `u64` and the
+	///     joiner
+	///
+	/// `max_length` is not included since it only decides whether `encode` fails. Any change to
+	/// this format is a breaking change.
+	pub fn config_fingerprint(&self) -> u64 {
+		const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+		const FNV_PRIME: u64 = 0x100000001b3;
+
+		let alphabet =
+			Self::prepare_alphabet(&self.original_alphabet, self.alphabet_offset, self.seed);
+		let mut bytes: Vec<u8> = alphabet.into_iter().collect::<String>().into_bytes();
+		bytes.push(0);
+		bytes.push(self.min_length);
+		#[cfg(feature = "blocklist")]
+		{
+			let mut words: Vec<&String> = self.blocklist.iter().collect();
+			words.sort();
+			for word in words {
+				bytes.extend(word.bytes());
+				bytes.push(0);
+			}
+		}
+		if let Some(prefix) = &self.prefix {
+			bytes.extend(prefix.bytes());
+		}
+		if let PaddingStrategy::RepeatChar(c) = self.padding {
+			bytes.push(0);
+			bytes.extend(c.to_string().bytes());
+		}
+		#[cfg(feature = "blocklist")]
+		if self.blocklist_mode != BlocklistMode::Substring {
+			bytes.push(1);
+			bytes.push(self.blocklist_mode as u8);
+		}
+		#[cfg(feature = "blocklist")]
+		if self.blocklist_case_sensitive {
+			bytes.push(2);
+		}
+		#[cfg(feature = "blocklist")]
+		if let Some(substitutions) = &self.blocklist_substitutions {
+			bytes.push(3);
+			let mut pairs: Vec<(&char, &char)> = substitutions.iter().collect();
+			pairs.sort();
+			for (from, to) in pairs {
+				bytes.extend(from.to_string().bytes());
+				bytes.extend(to.to_string().bytes());
+			}
+		}
+		#[cfg(feature = "regex")]
+		if !self.blocklist_patterns.is_empty() {
+			bytes.push(4);
+			for pattern in self.blocklist_patterns.patterns() {
+				bytes.extend(pattern.bytes());
+				bytes.push(0);
+			}
+		}
+		if let Some(sep) = self.prefix_separator {
+			bytes.push(5);
+			bytes.extend(sep.to_string().bytes());
+		}
+		if self.shuffle_rounds != 1 {
+			bytes.push(6);
+			bytes.push(self.shuffle_rounds);
+		}
+		#[cfg(feature = "blocklist")]
+		if !self.allowlist.is_empty() {
+			bytes.push(7);
+			let mut entries: Vec<&String> = self.allowlist.iter().collect();
+			entries.sort();
+			for entry in entries {
+				bytes.extend(entry.bytes());
+				bytes.push(0);
+			}
+		}
+		if !self.reserved_ids.is_empty() {
+			bytes.push(8);
+			let mut ids: Vec<&String> = self.reserved_ids.iter().collect();
+			ids.sort();
+			for id in ids {
+				bytes.extend(id.bytes());
+				bytes.push(0);
+			}
+		}
+		if let Some(fixed_length) = self.fixed_length {
+			bytes.push(9);
+			bytes.extend(fixed_length.to_be_bytes());
+		}
+		if self.checksum {
+			bytes.push(10);
+		}
+		if let Some((size, joiner)) = self.group {
+			bytes.push(11);
+			bytes.extend((size as u64).to_be_bytes());
+			bytes.extend(joiner.to_string().bytes());
+		}
+
+		bytes
+			.iter()
+			.fold(FNV_OFFSET_BASIS, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+	}
+
+	pub fn encode(&self, numbers: &[u64]) -> Result<String> {
+		self.encode_with_min_length(numbers, self.min_length.into())
+	}
+
+	/// Encodes with `min_length` in place of the configured one, e.g. for a few longer IDs.
+	/// `max_length` and `fixed_length` still apply.
+	pub fn encode_with_min_length(&self, numbers: &[u64], min_length: u16) -> Result<String> {
+		let mut scratch = EncodeScratch::new();
+		self.encode_padded(numbers, min_length as usize, self.fixed_length, &mut scratch)?;
+		Ok(scratch.id)
+	}
+
+	/// Encodes with `min_length` and `fixed_length` shortened by `reserved` characters, for IDs
+	/// the caller prepends that many characters to.
+	pub(crate) fn encode_reserving(&self, numbers: &[u64], reserved: u16) -> Result<String> {
+		let min_length = u16::from(self.min_length).saturating_sub(reserved);
+		let fixed_length = self.fixed_length.map(|len| len.saturating_sub(reserved));
+		let mut scratch = EncodeScratch::new();
+		self.encode_padded(numbers, min_length as usize, fixed_length, &mut scratch)?;
+		Ok(scratch.id)
+	}
+
+	/// Like `encode`, but also returns how many IDs were generated: 1, plus one for every
+	/// candidate that hit the blocklist. Useful for spotting blocklists that cause many retries.
+	pub fn encode_with_stats(&self, numbers: &[u64]) -> Result<(String, usize)> {
+		let mut scratch = EncodeScratch::new();
+		let attempts =
+			self.encode_padded(numbers, self.min_length.into(), self.fixed_length, &mut scratch)?;
+		Ok((scratch.id, attempts))
+	}
+
+	/// Like `encode`, but builds the ID in `scratch` and returns it from there. Reusing one
+	/// `scratch` per thread makes encoding allocation-free once its buffers have grown, except
+	/// with `verify_roundtrip`, which decodes every ID.
+	pub fn encode_with_scratch<'a>(
+		&self,
+		numbers: &[u64],
+		scratch: &'a mut EncodeScratch,
+	) -> Result<&'a str> {
+		self.encode_padded(numbers, self.min_length.into(), self.fixed_length, scratch)?;
+		Ok(&scratch.id)
+	}
+
+	/// Leaves the ID in `scratch.id` and returns the number of generated candidates.
+	fn encode_padded(
+		&self,
+		numbers: &[u64],
+		min_length: usize,
+		fixed_length: Option<u16>,
+		scratch: &mut EncodeScratch,
+	) -> Result<usize> {
+		scratch.id.clear();
+		if numbers.is_empty() {
+			return if self.allow_empty { Ok(0) } else { Err(Error::EmptyInput) };
+		}
+
+		let attempts = self.encode_numbers(numbers, min_length, fixed_length, scratch)?;
+		if self.group.is_some() || self.prefix.is_some() {
+			let EncodeScratch { id, grouped, .. } = scratch;
+			grouped.clear();
+			if let Some(prefix) = &self.prefix {
+				grouped.push_str(prefix);
+			}
+			match self.group {
+				Some((size, joiner)) => {
+					for (i, c) in id.chars().enumerate() {
+						if i > 0 && i % size == 0 {
+							grouped.push(joiner);
+						}
+						grouped.push(c);
+					}
+				}
+				None => grouped.push_str(id),
+			}
+			std::mem::swap(id, grouped);
+		}
+
+		if self.verify_roundtrip && self.decode(&scratch.id) != numbers {
+			return Err(Error::RoundtripFailed);
+		}
+
+		Ok(attempts)
+	}
+
+	/// Encodes a non-decreasing list as its first number followed by the differences between
+	/// neighbours, which gives much shorter IDs for clustered numbers. Decode with
+	/// [`Sqids::decode_sorted`]; unsorted input returns [`Error::NotSorted`].
+	pub fn encode_sorted(&self, sorted: &[u64]) -> Result<String> {
+		let mut deltas = Vec::with_capacity(sorted.len());
+		let mut previous = 0;
+		for &number in sorted {
+			if number < previous {
+				return Err(Error::NotSorted);
+			}
+			deltas.push(number - previous);
+			previous = number;
+		}
+
+		self.encode(&deltas)
+	}
+
+	/// Encodes each batch and joins the IDs with `sep`, which fails with
+	/// [`Error::InvalidSeparator`] if it can appear in an ID: if it is in the alphabet or the
+	/// prefix, or is the group joiner or padding character. Splitting on `sep` gives the IDs back.
+	pub fn encode_batch_to_string(&self, batches: &[&[u64]], sep: char) -> Result<String> {
+		let in_prefix = self.prefix.as_ref().is_some_and(|prefix| prefix.contains(sep));
+		if self.alphabet.contains(&sep)
+			|| in_prefix
+			|| self.group.is_some_and(|(_, joiner)| joiner == sep)
+			|| self.padding == PaddingStrategy::RepeatChar(sep)
+		{
+			return Err(Error::InvalidSeparator(sep));
+		}
+
+		let mut scratch = EncodeScratch::new();
+		let mut joined = String::new();
+		for (i, numbers) in batches.iter().enumerate() {
+			if i > 0 {
+				joined.push(sep);
+			}
+			joined.push_str(self.encode_with_scratch(numbers, &mut scratch)?);
+		}
+
+		Ok(joined)
+	}
+
+	/// Encodes into `{prefix}{separator}{id}`, like `usr_86Rf07`, to tell resource types apart
+	/// with one configuration; see [`Options::prefix_separator`]. Fails with
+	/// [`Error::InvalidPrefix`] if `prefix` is empty or contains the separator, and with
+	/// [`Error::InvalidSeparator`] if the default `_` is in the alphabet.
+	pub fn encode_prefixed(&self, prefix: &str, numbers: &[u64]) -> Result<String> {
+		let sep = self.prefix_separator()?;
+		if prefix.is_empty() || prefix.contains(sep) {
+			return Err(Error::InvalidPrefix);
+		}
+
+		Ok(format!("{prefix}{sep}{}", self.encode(numbers)?))
+	}
+
+	/// Decodes an ID of [`Sqids::encode_prefixed`], or returns `None` if it doesn't start with
+	/// `expected_prefix` and the separator, or the rest doesn't decode (like
+	/// [`Sqids::try_decode`]).
+	pub fn decode_prefixed(&self, expected_prefix: &str, id: &str) -> Option<Vec<u64>> {
+		let sep = self.prefix_separator().ok()?;
+		let rest = id.strip_prefix(expected_prefix)?.strip_prefix(sep)?;
+		self.try_decode(rest).ok()
+	}
+
+	fn prefix_separator(&self) -> Result<char> {
+		match self.prefix_separator {
+			Some(sep) => Ok(sep),
+			None if self.alphabet.contains(&'_') => Err(Error::InvalidSeparator('_')),
+			None => Ok('_'),
+		}
+	}
+
+	/// Encodes `numbers` straight into `writer`, building the ID in an [`EncodeScratch`] kept per
+	/// thread, so no `String` is allocated for it once the scratch has grown. The ID is still
+	/// checked against the blocklist as a whole before anything is written; encoding errors are
+	/// returned as [`io::ErrorKind::InvalidInput`] wrapping the [`Error`].
+	pub fn encode_to_writer<W: Write>(&self, numbers: &[u64], writer: &mut W) -> io::Result<()> {
+		thread_local! {
+			static SCRATCH: RefCell<EncodeScratch> = RefCell::default();
+		}
+
+		let mut write = |scratch: &mut EncodeScratch| {
+			let id = self
+				.encode_with_scratch(numbers, scratch)
+				.map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+			writer.write_all(id.as_bytes())
+		};
+		SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+			Ok(mut scratch) => write(&mut scratch),
+			// a writer that encodes into another writer on the same thread
+			Err(_) => write(&mut EncodeScratch::new()),
+		})
+	}
+
+	/// Returns the length in characters of the ID that `encode` would produce for `numbers`,
+	/// without building it.
+	///
+	/// Blocklist re-encoding only changes which characters are used, never how many, so this is
+	/// exact whenever `encode` succeeds. It does not check `max_length` or the blocklist.
+	pub fn estimate_length(&self, numbers: &[u64]) -> usize {
+		if numbers.is_empty() {
+			return 0;
+		}
+
+		// every number is written in base `alphabet.len() - 1`, the separator is excluded
+		let base = self.alphabet.len() as u64 - 1;
+		let digits: usize = numbers
+			.iter()
+			.map(|&num| {
+				let mut len = 1;
+				let mut result = num / base;
+				while result > 0 {
+					len += 1;
+					result /= base;
+				}
+				len
+			})
+			.sum();
+
+		// prefix + digits + one separator between each pair of numbers + check character
+		let len = 1 + digits + numbers.len() - 1 + self.checksum as usize;
+		let len = len.max(self.min_length as usize).max(self.fixed_length.map_or(0, usize::from));
+		let joiners = self.group.map_or(0, |(size, _)| (len - 1) / size);
+		len + joiners + self.prefix.as_ref().map_or(0, |prefix| prefix.chars().count())
+	}
+
+	/// `id` without the surrounding whitespace if [`Options::trim_input`] is set.
+	fn trimmed<'a>(&self, id: &'a str) -> &'a str {
+		if !self.trim_input {
+			return id;
+		}
+
+		let outside = |c: char| c.is_ascii_whitespace() && !self.alphabet.contains(&c);
+		let in_prefix = |c: char| self.prefix.as_ref().is_some_and(|prefix| prefix.contains(c));
+		id.trim_start_matches(|c: char| outside(c) && !in_prefix(c)).trim_end_matches(outside)
+	}
+
+	/// Decodes `id`, or returns an empty list if it isn't valid. `decode("")` is always empty.
+	pub fn decode(&self, id: &str) -> Vec<u64> {
+		let mut decoder = Decoder::new(self, id);
+		let mut ret = Vec::with_capacity(decoder.size_hint().1.unwrap_or(0));
+		ret.extend(decoder.by_ref());
+
+		if decoder.failed {
+			return Vec::new();
+		}
+
+		if self.checksum && !ret.is_empty() {
+			match decoder.check {
+				Some((check, alphabet)) if alphabet[1 + self.checksum_index(&ret)] == check => {}
+				_ => return Vec::new(),
+			}
+		}
+
+		ret
+	}
+
+	/// Like [`Sqids::decode`], but into a buffer of `N` numbers, with the `fixed` feature. Fails
+	/// with [`Error::CapacityExceeded`] once `id` turns out to have more, and with
+	/// [`Error::InvalidId`] if it doesn't decode (except `""`). Only the result stays off the
+	/// heap; [`SqidsN`] decodes without allocating at all.
+	#[cfg(feature = "fixed")]
+	pub fn decode_into_array<const N: usize>(
+		&self,
+		id: &str,
+	) -> Result<arrayvec::ArrayVec<u64, N>> {
+		let mut decoder = Decoder::new(self, id);
+		let mut ret = arrayvec::ArrayVec::new();
+		for number in decoder.by_ref() {
+			ret.try_push(number).map_err(|_| Error::CapacityExceeded { capacity: N })?;
+		}
+
+		let checksum_ok = !self.checksum
+			|| ret.is_empty()
+			|| matches!(&decoder.check, Some((check, alphabet)) if alphabet[1 + self.checksum_index(&ret)] == *check);
+		if decoder.failed || !checksum_ok || (ret.is_empty() && !self.trimmed(id).is_empty()) {
+			return Err(Error::InvalidId);
+		}
+
+		Ok(ret)
+	}
+
+	/// Counts the numbers in `id` without converting them, which is cheaper than
+	/// `decode(id).len()`. Invalid IDs count 0, but a number that overflows a `u64` or a wrong
+	/// checksum isn't detected.
+	pub fn decode_count(&self, id: &str) -> usize {
+		let mut decoder = Decoder::new(self, id);
+		let mut count = 0;
+		while decoder.next_chunk().is_some() {
+			count += 1;
+		}
+
+		if decoder.failed {
+			0
+		} else {
+			count
+		}
+	}
+
+	/// Decodes an ID from [`Sqids::encode_sorted`] back into the absolute numbers. Returns an
+	/// empty `Vec` if they would overflow a `u64`.
+	pub fn decode_sorted(&self, id: &str) -> Vec<u64> {
+		let mut previous: u64 = 0;
+		self.decode(id)
+			.into_iter()
+			.map(|delta| {
+				previous = previous.checked_add(delta)?;
+				Some(previous)
+			})
+			.collect::<Option<Vec<u64>>>()
+			.unwrap_or_default()
+	}
+
+	/// Like [`Sqids::decode`], but fails with [`Error::PrefixMismatch`] if the ID doesn't start
+	/// with the configured prefix, with [`Error::InvalidId`] if the rest doesn't decode, and with
+	/// [`Error::BlockedId`] if [`Options::validate_blocklist_on_decode`] is set and the blocklist
+	/// rejects it.
+	pub fn try_decode(&self, id: &str) -> Result<Vec<u64>> {
+		let id = self.trimmed(id);
+		if id.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		if let Some(prefix) = &self.prefix {
+			if !id.starts_with(prefix.as_str()) {
+				return Err(Error::PrefixMismatch);
+			}
+		}
+
+		let numbers = self.decode(id);
+		if numbers.is_empty() {
+			return Err(Error::InvalidId);
+		}
+		self.check_decoded(id)?;
+
+		Ok(numbers)
+	}
+
+	/// Decodes an ID of exactly one number. IDs of any other count fail with
+	/// [`Error::UnexpectedArity`], and IDs that don't decode at all (except `""`, which encodes no
+	/// numbers) with [`Error::InvalidId`]. IDs without the configured prefix and blocked IDs fail
+	/// like in [`Sqids::try_decode`].
+	pub fn decode_one(&self, id: &str) -> Result<u64> {
+		let trimmed = self.trimmed(id);
+		if let Some(prefix) = &self.prefix {
+			if !trimmed.is_empty() && !trimmed.starts_with(prefix.as_str()) {
+				return Err(Error::PrefixMismatch);
+			}
+		}
+
+		let numbers = self.decode(id);
+		match numbers[..] {
+			[number] => self.check_decoded(id).map(|()| number),
+			[] if !self.trimmed(id).is_empty() => Err(Error::InvalidId),
+			_ => Err(Error::UnexpectedArity { expected: 1, actual: numbers.len() }),
+		}
+	}
+
+	/// Decodes `id` only if it is exactly what `encode` produces for its numbers, failing with
+	/// [`Error::InvalidId`] otherwise. Rules out IDs that happen to decode under the wrong
+	/// configuration, or with different padding.
+	pub fn decode_canonical(&self, id: &str) -> Result<Vec<u64>> {
+		let numbers = self.decode(id);
+		if numbers.is_empty() || self.encode(&numbers).ok().as_deref() != Some(id) {
+			return Err(Error::InvalidId);
+		}
+
+		Ok(numbers)
+	}
+
+	/// Checks that `id` is exactly what `encode` produces for some numbers, with an error
+	/// describing the first problem: [`Error::PrefixMismatch`], [`Error::InvalidCharacter`] with
+	/// the character and its position (in characters, counting the prefix), or
+	/// [`Error::InvalidId`] for anything else, like a bad checksum or different padding.
+	pub fn validate_id(&self, id: &str) -> Result<()> {
+		let prefix = self.prefix.as_deref().unwrap_or("");
+		let Some(rest) = id.strip_prefix(prefix) else {
+			return Err(Error::PrefixMismatch);
+		};
+
+		let allowed = |c: char| {
+			self.alphabet.contains(&c)
+				|| self.group.is_some_and(|(_, joiner)| joiner == c)
+				|| self.padding == PaddingStrategy::RepeatChar(c)
+		};
+		if let Some((i, character)) = rest.chars().enumerate().find(|&(_, c)| !allowed(c)) {
+			return Err(Error::InvalidCharacter {
+				character,
+				position: prefix.chars().count() + i,
+			});
+		}
+
+		self.decode_canonical(id).map(|_| ())
+	}
+
+	/// Decodes a canonical `id` and encodes its numbers with `target`, for migrating IDs between
+	/// configurations. [`Error::InvalidId`] always means `id` isn't valid under `self`; any other
+	/// error comes from encoding with `target`.
+	pub fn re_encode(&self, id: &str, target: &Sqids) -> Result<String> {
+		target.encode(&self.decode_canonical(id)?)
+	}
+
+	/// [`Sqids::re_encode`] for every ID, lazily and with one result per ID.
+	pub fn re_encode_all<'a, I>(
+		&'a self,
+		ids: I,
+		target: &'a Sqids,
+	) -> impl Iterator<Item = Result<String>> + 'a
+	where
+		I: IntoIterator + 'a,
+		I::Item: AsRef<str>,
+	{
+		ids.into_iter().map(move |id| self.re_encode(id.as_ref(), target))
+	}
+
+	/// Decodes every line of `text`, one ID per line. Lines end with `\n` or `\r\n`, and blank
+	/// lines (including a trailing newline) are skipped, so an invalid ID is the only way to get
+	/// an empty list.
+	pub fn decode_all_lines(&self, text: &str) -> Vec<Vec<u64>> {
+		self.decode_lines(text).map(|(_, numbers)| numbers).collect()
+	}
+
+	/// Like [`Sqids::decode_all_lines`], but lazily and with the 1-based number of each line, to
+	/// report the lines that don't decode.
+	pub fn decode_lines<'a>(
+		&'a self,
+		text: &'a str,
+	) -> impl Iterator<Item = (usize, Vec<u64>)> + 'a {
+		text.split('\n')
+			.enumerate()
+			.map(|(i, line)| (i + 1, line.strip_suffix('\r').unwrap_or(line)))
+			.filter(|(_, line)| !line.trim().is_empty())
+			.map(|(number, line)| (number, self.decode(line)))
+	}
+
+	/// Yields the numbers of `id` one at a time, shuffling the alphabet only as far as needed.
+	/// Unlike [`Sqids::decode`], numbers before an overflowing chunk are still yielded and the
+	/// checksum isn't verified.
+	pub fn decode_iter<'a>(&'a self, id: &'a str) -> impl Iterator<Item = u64> + 'a {
+		Decoder::new(self, id)
+	}
+
+	/// The first number of `id`, decoding nothing after it; like [`Sqids::decode_iter`], the
+	/// checksum isn't verified.
+	pub fn decode_first(&self, id: &str) -> Option<u64> {
+		Decoder::new(self, id).next()
+	}
+
+	/// Returns whether `id` would be rejected by the blocklist, using the same matching rules as
+	/// `encode`, see [`BlocklistMode`]. Matching is case-insensitive unless
+	/// [`Options::blocklist_case_sensitive`] is set. With [`Options::matcher`], the matcher
+	/// decides instead.
+	#[cfg(feature = "blocklist")]
+	pub fn is_blocked(&self, id: &str) -> bool {
+		self.is_blocked_id(id)
+	}
+
+	/// The blocklist word (or with the `regex` feature, pattern) `candidate` matches under the
+	/// rules of [`Sqids::is_blocked`], e.g. to vet user-chosen slugs. [`Options::matcher`] is not
+	/// consulted, since it can't name a word.
+	#[cfg(feature = "blocklist")]
+	pub fn blocked_word_in(&self, candidate: &str) -> Option<&str> {
+		self.blocked_word(candidate, &mut String::new(), &mut String::new())
+	}
+
+	/// Leaves the ID in `scratch.id` and returns the number of generated candidates.
+	fn encode_numbers(
+		&self,
+		numbers: &[u64],
+		min_length: usize,
+		fixed_length: Option<u16>,
+		scratch: &mut EncodeScratch,
+	) -> Result<usize> {
+		let max_regen_attempts = self.max_regen_attempts.unwrap_or(self.alphabet.len());
+		let mut increment = 0;
+		// `Sqids::new` makes sure that `fixed_length` is within `min_length..=max_length`
+		let min_length = min_length.max(fixed_length.map_or(0, usize::from));
+		let max_length = fixed_length.or(self.max_length);
+
+		#[cfg(feature = "tracing")]
+		let span = tracing::debug_span!(
+			"encode_numbers",
+			count = numbers.len(),
+			retries = tracing::field::Empty
+		)
+		.entered();
+
+		loop {
+			let len = self.generate_id(numbers, increment, min_length, scratch);
+
+			// re-encoding never shortens the ID, so there is no point retrying past the limit
+			if let Some(max_length) = max_length {
+				if len > max_length as usize {
+					return Err(Error::MaxLengthExceeded);
+				}
+			}
+
+			//如果是非法的id，那么就增加步长，重新来一次
+			// the reserved ID or blocklist word, or `None` for the matcher
+			let reserved = self.reserved_ids.get(scratch.id.as_str()).map(String::as_str);
+			let rejected = match (reserved, &self.matcher) {
+				(Some(reserved), _) => Some(Some(reserved)),
+				(None, Some(matcher)) => matcher.0.is_blocked(&scratch.id).then_some(None),
+				(None, None) => self
+					.blocked_word(&scratch.id, &mut scratch.lowercase, &mut scratch.substituted)
+					.map(Some),
+			};
+			let Some(word) = rejected else {
+				#[cfg(feature = "tracing")]
+				span.record("retries", increment);
+				return Ok(increment + 1);
+			};
+
+			#[cfg(feature = "tracing")]
+			tracing::trace!(word, attempt = increment + 1, "ID rejected by the blocklist");
+
+			if increment >= max_regen_attempts {
+				#[cfg(feature = "tracing")]
+				span.record("retries", increment);
+				let attempts = increment + 1;
+				return Err(match word {
+					Some(word) => Error::BlocklistMaxAttempts { attempts, word: word.to_string() },
+					None => Error::MatcherMaxAttempts { attempts },
+				});
+			}
+			increment += 1;
+		}
+	}
+
+	/// Generates the candidate for `increment` into `scratch.id` and returns its length in
+	/// characters.
+	fn generate_id(
+		&self,
+		numbers: &[u64],
+		increment: usize,
+		min_length: usize,
+		scratch: &mut EncodeScratch,
+	) -> usize {
+		let EncodeScratch { alphabet, digits, id, .. } = scratch;
+
+		//将numbers的长度作为初始值
+		// v = numbers[i]
+		// a = a + i + self.alphabet[v % self.alphabet.len()]
+		let mut offset = numbers.iter().enumerate().fold(numbers.len(), |a, (i, &v)| {
+			self.alphabet[v as usize % self.alphabet.len()] as usize + i + a // a + i + alphabet[v % len(alphabet)]
+		}) % self.alphabet.len();
+
+		//计算出最终的offset
+		offset = (offset + increment) % self.alphabet.len();
+		//在offset这个位置将整个alphabet进行前后调换
+		alphabet.clear();
+		alphabet.extend_from_slice(&self.alphabet[offset..]);
+		alphabet.extend_from_slice(&self.alphabet[..offset]);
+		//取出字符表第一个字符，作为前缀字符，放在生成的ID的最前面，用来作ID首字符
+
+		let prefix = alphabet[0];
+		//将整个字符表进行逆转
+		alphabet.reverse();
+		//将prefix放入ID
+		id.clear();
+		id.push(prefix);
+		// counted separately, since multibyte alphabets make `id.len()` a byte count
+		let mut len = 1;
+		//开始遍历numbers序列
+		for (i, &num) in numbers.iter().enumerate() {
+			push_id(num, &alphabet[1..], digits, id); //使用除了第一个字符以外的字符表进行转换
+			len += digits.len();
+
+			if i < numbers.len() - 1 {
+				id.push(alphabet[0]); //放入分割符号
+				len += 1;
+				self.shuffle_next(alphabet, offset, i); //再次洗牌
+			}
+		}
+
+		if self.checksum {
+			// never the separator, so decode still finds the end of the last number
+			id.push(alphabet[1 + self.checksum_index(numbers)]);
+			len += 1;
+		}
+
+		if min_length > len {
+			//需要生成最小字符串大于生成的id长度
+			id.push(alphabet[0]); //继续添加分割符号
+			len += 1;
+
+			if let PaddingStrategy::RepeatChar(c) = self.padding {
+				id.extend(std::iter::repeat_n(c, min_length.saturating_sub(len)));
+				len = len.max(min_length);
+			}
+
+			let mut shuffles = numbers.len() - 1;
+			while min_length > len {
+				self.shuffle_next(alphabet, offset, shuffles); //洗牌
+				shuffles += 1;
+
+				let slice_len = min(min_length - len, alphabet.len());
+				id.extend(&alphabet[..slice_len]); //填充垃圾字符串
+				len += slice_len;
+			}
+		}
+
+		len
+	}
+
+	/// Folds every base `alphabet.len() - 1` digit `d` of every number (least significant first)
+	/// into `c = (c * (base - 1) + d) % base`, starting from `numbers.len() % base`. The
+	/// multiplier is coprime with the base, so any single mistyped digit changes the result.
+	fn checksum_index(&self, numbers: &[u64]) -> usize {
+		let base = self.alphabet.len() as u64 - 1;
+		let mut checksum = numbers.len() as u64 % base;
+
+		for &num in numbers {
+			let mut result = num;
+			loop {
+				checksum = (checksum * (base - 1) + result % base) % base;
+				result /= base;
+
+				if result == 0 {
+					break;
+				}
+			}
+		}
+
+		checksum as usize
+	}
+
+	/// Replaces every character outside of the alphabet whose other ASCII case is in it.
+	fn fold_case<'a>(&self, id: Cow<'a, str>) -> Cow<'a, str> {
+		if id.chars().all(|c| self.alphabet.contains(&c)) {
+			return id;
+		}
+
+		id.chars()
+			.map(|c| {
+				let other = if c.is_ascii_lowercase() {
+					c.to_ascii_uppercase()
+				} else {
+					c.to_ascii_lowercase()
+				};
+				if !self.alphabet.contains(&c) && self.alphabet.contains(&other) {
+					other
+				} else {
+					c
+				}
+			})
+			.collect::<String>()
+			.into()
+	}
+
+	/// The alphabet the spec shuffle starts from: rotated by `offset`, then keyed with the
+	/// little-endian bytes of `seed`.
+	fn prepare_alphabet(alphabet: &str, offset: usize, seed: Option<u64>) -> Vec<char> {
+		let mut alphabet: Vec<char> = alphabet.chars().collect();
+		let len = alphabet.len();
+		alphabet.rotate_left(offset % len);
+
+		match seed {
+			Some(seed) => Self::keyed_shuffle(&alphabet, &seed.to_le_bytes()),
+			None => alphabet,
+		}
+	}
 
+	/// The consistent shuffle with a key mixed in: one pass per key byte `b`, in order, each
+	/// swapping `chars[i]` with `chars[(i * j + chars[i] + chars[j] + b) % len]`.
+	fn keyed_shuffle(alphabet: &[char], key: &[u8]) -> Vec<char> {
+		let mut chars: Vec<char> = alphabet.to_vec();
+
+		for &b in key {
+			for i in 0..(chars.len() - 1) {
+				let j = chars.len() - 1 - i;
+				let r = (i as u64 * j as u64 + chars[i] as u64 + chars[j] as u64 + b as u64)
+					% chars.len() as u64;
+				chars.swap(i, r as usize);
+			}
 		}
 
 		chars
 	}
 
+	/// Applies [`Options::validate_blocklist_on_decode`] to an `id` that decoded.
+	#[cfg(feature = "blocklist")]
+	fn check_decoded(&self, id: &str) -> Result<()> {
+		if !self.validate_blocklist_on_decode {
+			return Ok(());
+		}
+
+		let mut id = Cow::Borrowed(self.trimmed(id));
+		if let Some((_, joiner)) = self.group {
+			if id.contains(joiner) {
+				id = Cow::Owned(id.replace(joiner, ""));
+			}
+		}
+
+		if self.is_blocked_id(&id) {
+			return Err(Error::BlockedId);
+		}
+		Ok(())
+	}
+
+	#[cfg(not(feature = "blocklist"))]
+	fn check_decoded(&self, _id: &str) -> Result<()> {
+		Ok(())
+	}
+
+	#[cfg(feature = "blocklist")]
 	fn is_blocked_id(&self, id: &str) -> bool {
-		let id = id.to_lowercase();
+		match &self.matcher {
+			Some(matcher) => matcher.0.is_blocked(id),
+			None => BlockedIdMatcher::is_blocked(self, id),
+		}
+	}
 
-		for word in &self.blocklist {
-			if word.len() <= id.len() {
-				if id.len() <= 3 || word.len() <= 3 {
-					if id == *word {
-						return true;
-					}
-				} else if word.chars().any(|c| c.is_ascii_digit()) {
-					if id.starts_with(word) || id.ends_with(word) {
-						return true;
-					}
-				} else if id.contains(word) {
-					return true;
+	#[cfg(not(feature = "blocklist"))]
+	fn blocked_word(
+		&self,
+		_id: &str,
+		_lowercase: &mut String,
+		_substituted: &mut String,
+	) -> Option<&str> {
+		None
+	}
+
+	/// Lowercases `id` into `lowercase` and returns the first blocklist word it matches, trying
+	/// the ID with the substitutions applied (written to `substituted`) if it matches none.
+	#[cfg(feature = "blocklist")]
+	fn blocked_word(
+		&self,
+		id: &str,
+		lowercase: &mut String,
+		substituted: &mut String,
+	) -> Option<&str> {
+		if self.blocklist_mode == BlocklistMode::Off {
+			return None;
+		}
+
+		let id = if self.blocklist_case_sensitive {
+			id
+		} else {
+			lowercase.clear();
+			if id.is_ascii() {
+				lowercase.extend(id.chars().map(|c| c.to_ascii_lowercase()));
+			} else {
+				lowercase.push_str(&id.to_lowercase());
+			}
+			lowercase.as_str()
+		};
+
+		if !self.allowlist.is_empty() && self.allowlist.contains(id) {
+			return None;
+		}
+
+		if let Some(word) = self.matching_word(id) {
+			return Some(word);
+		}
+
+		let substitutions = self.blocklist_substitutions.as_ref()?;
+		if !id.chars().any(|c| substitutions.contains_key(&c)) {
+			return None;
+		}
+		substituted.clear();
+		substituted.extend(id.chars().map(|c| *substitutions.get(&c).unwrap_or(&c)));
+		self.matching_word(substituted)
+	}
+
+	/// The first blocklist word `id` matches, or with the `regex` feature the first pattern.
+	#[cfg(feature = "blocklist")]
+	fn matching_word(&self, id: &str) -> Option<&str> {
+		let word = self.matching_list_word(id);
+		#[cfg(feature = "regex")]
+		let word = word.or_else(|| {
+			let index = self.blocklist_patterns.matches(id).into_iter().next()?;
+			Some(self.blocklist_patterns.patterns()[index].as_str())
+		});
+		word
+	}
+
+	#[cfg(feature = "blocklist")]
+	fn matching_list_word(&self, id: &str) -> Option<&str> {
+		self.blocklist_index.matching_word(id)
+	}
+}
+
+/// Walks an ID one chunk at a time, see [`Sqids::decode_iter`].
+struct Decoder<'a> {
+	sqids: &'a Sqids,
+	alphabet: Vec<char>,
+	// the offset the ID was encoded with, and how often `alphabet` has been shuffled since
+	offset: usize,
+	shuffles: usize,
+	// only owned when the case had to be folded
+	id: Cow<'a, str>,
+	pos: usize,
+	// check character of the last chunk and the alphabet that chunk was encoded with
+	check: Option<(char, Vec<char>)>,
+	failed: bool,
+}
+
+impl<'a> Decoder<'a> {
+	fn new(sqids: &'a Sqids, id: &'a str) -> Self {
+		let mut decoder = Decoder {
+			sqids,
+			alphabet: Vec::new(),
+			offset: 0,
+			shuffles: 0,
+			id: Cow::Borrowed(""),
+			pos: 0,
+			check: None,
+			failed: false,
+		};
+
+		let id = sqids.trimmed(id);
+		let id = match &sqids.prefix {
+			Some(prefix) => match id.strip_prefix(prefix.as_str()) {
+				Some(id) => id,
+				None => return decoder,
+			},
+			None => id,
+		};
+
+		let mut id = Cow::Borrowed(id);
+		if let Some((_, joiner)) = sqids.group {
+			if id.contains(joiner) {
+				id = Cow::Owned(id.replace(joiner, ""));
+			}
+		}
+
+		if let PaddingStrategy::RepeatChar(c) = sqids.padding {
+			id = match id {
+				Cow::Borrowed(id) => Cow::Borrowed(id.trim_end_matches(c)),
+				Cow::Owned(id) => Cow::Owned(id.trim_end_matches(c).to_string()),
+			};
+		}
+
+		if id.is_empty() {
+			return decoder;
+		}
+
+		if let Some(map) = &sqids.homoglyph_map {
+			if id.chars().any(|c| map.contains_key(&c)) {
+				id = Cow::Owned(id.chars().map(|c| *map.get(&c).unwrap_or(&c)).collect());
+			}
+		}
+
+		if sqids.case_insensitive_decode {
+			id = sqids.fold_case(id);
+		}
+
+		let alphabet_chars: CharSet = sqids.alphabet.iter().cloned().collect(); //字符表，转成set
+		if !id.chars().all(|c| alphabet_chars.contains(&c)) {
+			//如果发现有不存在的字符，就直接返回空数组
+			return decoder;
+		}
+
+		let prefix = id.chars().next().unwrap(); //取得首字符，确认prefix
+		let offset = sqids.alphabet.iter().position(|&c| c == prefix).unwrap(); //方向计算对应的offset
+		let alphabet: Vec<char> = sqids
+			.alphabet
+			.iter()
+			.cycle()
+			.skip(offset)
+			.take(sqids.alphabet.len())
+			.copied()
+			.collect();
+
+		decoder.alphabet = alphabet.into_iter().rev().collect(); //构建和编码时相同的字符表
+		decoder.offset = offset;
+		decoder.id = id;
+		decoder.pos = prefix.len_utf8(); //删除prefix
+		decoder
+	}
+
+	/// Splits off the digits of the next number, along with the alphabet they're written in and,
+	/// for the last number with a checksum, the check character.
+	fn next_chunk(&mut self) -> Option<(Range<usize>, Vec<char>, Option<char>)> {
+		let separator = *self.alphabet.first()?;
+		let start = self.pos;
+		let id = &self.id[start..];
+		if id.is_empty() || id.starts_with(separator) {
+			self.pos = self.id.len();
+			return None;
+		}
+
+		//如果存在多个numbers编码后的ID，那么就存在多个chunk，下一轮的separator会变
+		let (end, alphabet) = match id.find(separator) {
+			Some(len) => {
+				let mut next = self.alphabet.clone();
+				self.sqids.shuffle_next(&mut next, self.offset, self.shuffles); //对字符表进行洗牌
+				self.shuffles += 1;
+				self.pos = start + len + separator.len_utf8();
+				(start + len, std::mem::replace(&mut self.alphabet, next))
+			}
+			None => {
+				self.pos = self.id.len();
+				(self.id.len(), std::mem::take(&mut self.alphabet))
+			}
+		};
+		let rest = &self.id[self.pos..];
+
+		// the last number is followed by nothing or by padding, which starts with the next
+		// separator; with a checksum, its chunk ends with the check character
+		if self.sqids.checksum && (rest.is_empty() || rest.starts_with(self.alphabet[0])) {
+			let check = self.id[..end].chars().next_back();
+			let digits = start..end - check.map_or(0, char::len_utf8);
+			if digits.is_empty() {
+				return self.fail();
+			}
+			self.pos = self.id.len();
+			return Some((digits, alphabet, check));
+		}
+
+		Some((start..end, alphabet, None))
+	}
+
+	fn fail<T>(&mut self) -> Option<T> {
+		self.failed = true;
+		self.pos = self.id.len();
+		None
+	}
+}
+
+impl Iterator for Decoder<'_> {
+	type Item = u64;
+
+	fn next(&mut self) -> Option<u64> {
+		let (digits, alphabet, check) = self.next_chunk()?;
+
+		match to_number(&self.id[digits], &alphabet[1..]) {
+			Some(number) => {
+				if let Some(check) = check {
+					self.check = Some((check, alphabet));
 				}
+				Some(number) //反转成数字
 			}
+			None => self.fail(),
 		}
+	}
 
-		false
+	/// Each number takes at least one character and is separated from the next one, so the
+	/// rest of the ID holds at most half as many numbers as it has bytes (rounded up). Padding
+	/// makes this an overestimate.
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, Some((self.id.len() - self.pos).div_ceil(2)))
 	}
 }
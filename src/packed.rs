@@ -0,0 +1,85 @@
+//! Lists of mostly small numbers packed into fewer, larger ones before encoding.
+
+use crate::{Error, Result, Sqids};
+
+impl Sqids {
+	/// Encodes `numbers` packed into fewer numbers, which gives much shorter IDs for lists of many
+	/// numbers below 128, e.g. 4 characters instead of 6 for `[1, 2, 3]`, and may give slightly
+	/// longer ones for lists of large numbers. Decode with [`Sqids::decode_packed`]; `decode`
+	/// returns the packed numbers. The format, for other ports:
+	///
+	/// 1. Each number is written as unsigned LEB128: 7 bits per byte, least significant first,
+	///    with the high bit set on every byte but the last. The bytes are concatenated.
+	/// 2. If the last byte is `0x00` (the list ends with 0), a `0x80` byte is appended, so the
+	///    stream never ends in a zero byte.
+	/// 3. The stream is cut into chunks of 8 bytes, the last one possibly shorter, and each chunk
+	///    is read as a little-endian `u64`. These numbers are encoded as usual.
+	///
+	/// Decoding reverses this; the last chunk is as long as its number's significant bytes.
+	pub fn encode_packed(&self, numbers: &[u64]) -> Result<String> {
+		let mut bytes = Vec::with_capacity(numbers.len());
+		for &number in numbers {
+			let mut rest = number;
+			while rest >= 0x80 {
+				bytes.push(rest as u8 | 0x80);
+				rest >>= 7;
+			}
+			bytes.push(rest as u8);
+		}
+		if bytes.last() == Some(&0) {
+			bytes.push(0x80);
+		}
+
+		let chunks: Vec<u64> = bytes
+			.chunks(8)
+			.map(|chunk| {
+				let mut buf = [0; 8];
+				buf[..chunk.len()].copy_from_slice(chunk);
+				u64::from_le_bytes(buf)
+			})
+			.collect();
+		self.encode(&chunks)
+	}
+
+	/// Decodes an ID of [`Sqids::encode_packed`]. Fails like [`Sqids::try_decode`], and with
+	/// [`Error::InvalidId`] if the numbers aren't a packed list.
+	pub fn decode_packed(&self, id: &str) -> Result<Vec<u64>> {
+		let chunks = self.try_decode(id)?;
+		let Some((&last, full)) = chunks.split_last() else {
+			return Ok(Vec::new());
+		};
+		if last == 0 {
+			return Err(Error::InvalidId);
+		}
+
+		let mut bytes: Vec<u8> = full.iter().flat_map(|chunk| chunk.to_le_bytes()).collect();
+		let last = last.to_le_bytes();
+		bytes.extend(&last[..8 - last.iter().rev().take_while(|&&b| b == 0).count()]);
+		if let [.., 0, 0x80] = bytes[..] {
+			bytes.pop();
+		}
+
+		let mut numbers = Vec::new();
+		let (mut number, mut shift) = (0u64, 0);
+		for &byte in &bytes {
+			let bits = (byte & 0x7f) as u64;
+			// more than 64 bits, or a trailing zero group that makes the encoding longer
+			if shift == 63 && bits > 1 || shift > 63 || shift > 0 && byte == 0 {
+				return Err(Error::InvalidId);
+			}
+			number |= bits << shift;
+
+			if byte & 0x80 == 0 {
+				numbers.push(number);
+				(number, shift) = (0, 0);
+			} else {
+				shift += 7;
+			}
+		}
+		if shift > 0 {
+			return Err(Error::InvalidId);
+		}
+
+		Ok(numbers)
+	}
+}
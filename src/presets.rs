@@ -0,0 +1,9 @@
+//! Ready-made [`Options`].
+
+use crate::{alphabet, Options};
+
+/// The default options with [`alphabet::NO_LOOKALIKES`], so IDs never contain `0`, `O`, `1`,
+/// `l` or `I`.
+pub fn unambiguous() -> Options {
+	Options { alphabet: alphabet::NO_LOOKALIKES.to_string(), ..Options::default() }
+}
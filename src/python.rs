@@ -0,0 +1,62 @@
+//! Python bindings, enabled with the `python` feature and built with
+//! [maturin](https://www.maturin.rs) (`pip install .` uses the bundled `pyproject.toml`).
+//!
+//! Numbers are converted to `u64`, so negative ints and ints above `2**64 - 1` raise
+//! `OverflowError`. Crate errors are raised as `sqids.SqidsError`, a `ValueError` subclass.
+
+use std::collections::HashSet;
+
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*};
+
+use crate::{Error, Options, Sqids};
+
+create_exception!(sqids, SqidsError, PyValueError);
+
+impl From<Error> for PyErr {
+	fn from(err: Error) -> PyErr {
+		SqidsError::new_err(err.to_string())
+	}
+}
+
+#[pyclass(name = "Sqids", module = "sqids", frozen)]
+pub struct PySqids {
+	inner: Sqids,
+}
+
+#[pymethods]
+impl PySqids {
+	#[new]
+	#[pyo3(signature = (alphabet = None, min_length = None, blocklist = None))]
+	fn new(
+		alphabet: Option<String>,
+		min_length: Option<u8>,
+		blocklist: Option<HashSet<String>>,
+	) -> PyResult<Self> {
+		let inner = Sqids::new(Some(Options::new(alphabet, min_length, blocklist)))?;
+		Ok(PySqids { inner })
+	}
+
+	fn encode(&self, numbers: Vec<u64>) -> PyResult<String> {
+		Ok(self.inner.encode(&numbers)?)
+	}
+
+	fn decode(&self, id: &str) -> Vec<u64> {
+		self.inner.decode(id)
+	}
+
+	fn __repr__(&self) -> String {
+		format!(
+			"Sqids(alphabet={:?}, min_length={})",
+			self.inner.alphabet(),
+			self.inner.min_length()
+		)
+	}
+}
+
+#[pymodule]
+#[pyo3(name = "sqids")]
+fn sqids_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+	m.add_class::<PySqids>()?;
+	m.add("SqidsError", m.py().get_type::<SqidsError>())?;
+	Ok(())
+}
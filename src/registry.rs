@@ -0,0 +1,78 @@
+//! Several configurations told apart by their ID prefix, like `usr_` and `ord_`.
+
+use std::collections::BTreeMap;
+
+use crate::{Error, Result, Sqids};
+
+/// Maps prefixes to the `Sqids` used for IDs starting with them.
+#[derive(Clone, Debug, Default)]
+pub struct SqidsRegistry {
+	entries: BTreeMap<String, Sqids>,
+}
+
+impl SqidsRegistry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers `sqids` for IDs starting with `prefix`, replacing any prefix it was built with.
+	///
+	/// Fails with [`Error::InvalidPrefix`] under the same rule as [`Sqids::for_tenant`], and with
+	/// [`Error::AmbiguousPrefix`] if `prefix` is already registered, or if one of the two prefixes
+	/// extends the other with characters the shorter one's `decode` reads as part of an ID (so an
+	/// ID could belong to either).
+	pub fn register(&mut self, prefix: impl Into<String>, sqids: Sqids) -> Result<()> {
+		let prefix = prefix.into();
+		match prefix.chars().last() {
+			Some(last) if !sqids.reads_as_id(last) => {}
+			_ => return Err(Error::InvalidPrefix),
+		}
+
+		for (existing, other) in &self.entries {
+			let ambiguous = if let Some(rest) = prefix.strip_prefix(existing.as_str()) {
+				rest.chars().all(|c| other.reads_as_id(c))
+			} else if let Some(rest) = existing.strip_prefix(prefix.as_str()) {
+				rest.chars().all(|c| sqids.reads_as_id(c))
+			} else {
+				false
+			};
+
+			if ambiguous {
+				return Err(Error::AmbiguousPrefix { prefix, existing: existing.clone() });
+			}
+		}
+
+		let sqids = Sqids { prefix: Some(prefix.clone()), ..sqids };
+		self.entries.insert(prefix, sqids);
+		Ok(())
+	}
+
+	/// The registered prefixes, in ascending order.
+	pub fn prefixes(&self) -> impl Iterator<Item = &str> {
+		self.entries.keys().map(String::as_str)
+	}
+
+	pub fn get(&self, prefix: &str) -> Option<&Sqids> {
+		self.entries.get(prefix)
+	}
+
+	/// Encodes with the `Sqids` registered for `prefix`, failing with [`Error::UnknownPrefix`] if
+	/// there is none.
+	pub fn encode(&self, prefix: &str, numbers: &[u64]) -> Result<String> {
+		self.get(prefix).ok_or_else(|| Error::UnknownPrefix(prefix.to_string()))?.encode(numbers)
+	}
+
+	/// Decodes `id` with the `Sqids` of the longest registered prefix it starts with, returning
+	/// that prefix along with the numbers. Fails with [`Error::PrefixMismatch`] if no prefix
+	/// matches, otherwise as [`Sqids::try_decode`].
+	pub fn decode(&self, id: &str) -> Result<(String, Vec<u64>)> {
+		let (prefix, sqids) = self
+			.entries
+			.iter()
+			.filter(|(prefix, _)| id.starts_with(prefix.as_str()))
+			.max_by_key(|(prefix, _)| prefix.len())
+			.ok_or(Error::PrefixMismatch)?;
+
+		Ok((prefix.clone(), sqids.try_decode(id)?))
+	}
+}
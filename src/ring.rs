@@ -0,0 +1,40 @@
+//! Decoding IDs from several configurations, for rotating to a new alphabet.
+
+use crate::{Result, Sqids};
+
+/// A primary `Sqids` used for encoding, plus older configurations still accepted when decoding.
+#[derive(Clone, Debug)]
+pub struct SqidsRing {
+	configs: Vec<Sqids>,
+}
+
+impl SqidsRing {
+	/// `fallbacks` are tried in order after `primary`.
+	pub fn new(primary: Sqids, fallbacks: impl IntoIterator<Item = Sqids>) -> Self {
+		let mut configs = vec![primary];
+		configs.extend(fallbacks);
+		SqidsRing { configs }
+	}
+
+	pub fn primary(&self) -> &Sqids {
+		&self.configs[0]
+	}
+
+	/// All configurations, the primary first.
+	pub fn configs(&self) -> &[Sqids] {
+		&self.configs
+	}
+
+	/// Encodes with the primary configuration.
+	pub fn encode(&self, numbers: &[u64]) -> Result<String> {
+		self.primary().encode(numbers)
+	}
+
+	/// Returns the numbers and the index of the first configuration `id` is canonical for (see
+	/// [`Sqids::decode_canonical`]), `0` being the primary.
+	pub fn decode(&self, id: &str) -> Option<(Vec<u64>, usize)> {
+		self.configs.iter().enumerate().find_map(|(index, sqids)| {
+			sqids.decode_canonical(id).ok().map(|numbers| (numbers, index))
+		})
+	}
+}
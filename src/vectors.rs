@@ -0,0 +1,130 @@
+//! Test vectors generated from a configuration, for pinning other implementations to the exact
+//! IDs this crate produces.
+
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Sqids;
+
+/// How many numbers the blocklist search tries before giving up, one encode each.
+const BLOCKLIST_SEARCH_LIMIT: usize = 10_000;
+
+/// Controls [`Sqids::generate_test_vectors`]. The same spec and configuration always give the
+/// same vectors.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VectorSpec {
+	/// How many vectors with random numbers to generate.
+	pub count: usize,
+	/// The range of the random numbers. Keep it below 2^53 for ports that use floating-point
+	/// numbers, like JavaScript.
+	pub values: RangeInclusive<u64>,
+	/// The range of how many numbers each random vector has.
+	pub numbers: RangeInclusive<usize>,
+	/// Seeds the generator, so different seeds give different corpora.
+	pub seed: u64,
+	/// Adds up to this many single-number vectors whose first candidate ID hit the blocklist,
+	/// searching the first 10,000 numbers of `values` and stopping once enough are found. That's
+	/// an encode per number tried, a few milliseconds in release builds. The default blocklist
+	/// blocks about one number in 10,000, the first being 15583, so start `values` where it blocks
+	/// several, like 250,000.
+	pub blocklist_cases: usize,
+	/// Adds the random vectors again for each of these lengths, encoded with
+	/// [`Sqids::encode_with_min_length`].
+	pub min_lengths: Vec<u16>,
+}
+
+impl Default for VectorSpec {
+	fn default() -> Self {
+		VectorSpec {
+			count: 100,
+			values: 0..=1_000_000,
+			numbers: 1..=5,
+			seed: 0,
+			blocklist_cases: 10,
+			min_lengths: Vec::new(),
+		}
+	}
+}
+
+/// `numbers` encode to `id`, with `min_length` in place of the configured one if set.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TestVector {
+	pub numbers: Vec<u64>,
+	pub id: String,
+	#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+	pub min_length: Option<u16>,
+}
+
+/// SplitMix64, which is tiny and stable, so corpora don't change between releases.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+		z ^ (z >> 31)
+	}
+
+	fn in_range(&mut self, range: &RangeInclusive<u64>) -> u64 {
+		match range.end().saturating_sub(*range.start()).checked_add(1) {
+			Some(span) => range.start() + self.next() % span,
+			None => self.next(),
+		}
+	}
+}
+
+impl Sqids {
+	/// Generates vectors for this configuration as described by `spec`. Numbers that fail to
+	/// encode (blocklist exhausted or `max_length` exceeded) are left out. With the `serde`
+	/// feature, the vectors serialize to JSON as `{"numbers": [...], "id": "..."}`; see
+	/// `examples/test_vectors.rs`.
+	///
+	/// The random numbers come from SplitMix64 seeded with `spec.seed`, so a spec is a stable
+	/// name for its corpus.
+	pub fn generate_test_vectors(&self, spec: VectorSpec) -> Vec<TestVector> {
+		let mut rng = SplitMix64(spec.seed);
+		let counts = *spec.numbers.start() as u64..=*spec.numbers.end() as u64;
+
+		let random: Vec<Vec<u64>> = (0..spec.count)
+			.map(|_| {
+				let count = rng.in_range(&counts) as usize;
+				(0..count).map(|_| rng.in_range(&spec.values)).collect()
+			})
+			.collect();
+
+		let mut vectors: Vec<TestVector> = random
+			.iter()
+			.filter_map(|numbers| {
+				let id = self.encode(numbers).ok()?;
+				Some(TestVector { numbers: numbers.clone(), id, min_length: None })
+			})
+			.collect();
+
+		let blocked = spec
+			.values
+			.clone()
+			.take(BLOCKLIST_SEARCH_LIMIT)
+			.filter_map(|number| match self.encode_with_stats(&[number]) {
+				Ok((id, attempts)) if attempts > 1 => {
+					Some(TestVector { numbers: vec![number], id, min_length: None })
+				}
+				_ => None,
+			})
+			.take(spec.blocklist_cases);
+		vectors.extend(blocked);
+
+		for &min_length in &spec.min_lengths {
+			vectors.extend(random.iter().filter_map(|numbers| {
+				let id = self.encode_with_min_length(numbers, min_length).ok()?;
+				Some(TestVector { numbers: numbers.clone(), id, min_length: Some(min_length) })
+			}));
+		}
+
+		vectors
+	}
+}
@@ -0,0 +1,88 @@
+//! IDs tagged with the version of the configuration that encoded them.
+
+use std::collections::BTreeMap;
+
+use crate::{Error, Result, Sqids};
+
+/// Several versions of a configuration, each ID starting with one character naming the version
+/// it was encoded with: the character at that index of the version's alphabet, as it was
+/// configured, so IDs stay within it. With the default alphabet versions go from 0 to 61, tagged
+/// `a` to `9`.
+///
+/// The tag counts towards `min_length` and `fixed_length`, so IDs of a version are as long as
+/// without tagging; `max_length` and `prefix` only apply to the part after it.
+#[derive(Clone, Debug)]
+pub struct VersionedSqids {
+	current: u8,
+	versions: BTreeMap<u8, Sqids>,
+}
+
+impl VersionedSqids {
+	/// `current` is the version new IDs are encoded with. Fails with
+	/// [`Error::VersionOutOfRange`] for a version not below the length of its alphabet, with
+	/// [`Error::AmbiguousVersionTag`] if two versions get the same tag, and with
+	/// [`Error::MissingVersion`] if `current` isn't one of `versions`.
+	pub fn new(current: u8, versions: impl IntoIterator<Item = (u8, Sqids)>) -> Result<Self> {
+		let versions: BTreeMap<u8, Sqids> = versions.into_iter().collect();
+
+		let mut tags: BTreeMap<char, u8> = BTreeMap::new();
+		for (&version, sqids) in &versions {
+			let tag = tag(version, sqids).ok_or(Error::VersionOutOfRange(version))?;
+			if let Some(&first) = tags.get(&tag) {
+				return Err(Error::AmbiguousVersionTag { tag, first, second: version });
+			}
+			tags.insert(tag, version);
+		}
+
+		if !versions.contains_key(&current) {
+			return Err(Error::MissingVersion(current));
+		}
+
+		Ok(VersionedSqids { current, versions })
+	}
+
+	pub fn current_version(&self) -> u8 {
+		self.current
+	}
+
+	/// The configuration of `version`, if there is one.
+	pub fn get(&self, version: u8) -> Option<&Sqids> {
+		self.versions.get(&version)
+	}
+
+	/// Encodes with the current version.
+	pub fn encode(&self, numbers: &[u64]) -> Result<String> {
+		let sqids = &self.versions[&self.current];
+		let id = sqids.encode_reserving(numbers, 1)?;
+		if id.is_empty() {
+			return Ok(id);
+		}
+
+		// `new` checked that the current version has a tag
+		Ok(format!("{}{id}", tag(self.current, sqids).unwrap()))
+	}
+
+	/// Returns the version `id` was encoded with and its numbers. Fails with
+	/// [`Error::UnknownVersion`] if its first character names no version, and otherwise as
+	/// [`Sqids::try_decode`]. The tag is read after trimming `id` like the version it names
+	/// would, if that version has [`Options::trim_input`](crate::Options::trim_input) set.
+	pub fn decode(&self, id: &str) -> Result<(u8, Vec<u64>)> {
+		let found = self.versions.iter().find_map(|(&version, sqids)| {
+			let rest = sqids.trimmed(id).strip_prefix(tag(version, sqids)?)?;
+			Some((version, sqids, rest))
+		});
+		let Some((version, sqids, rest)) = found else {
+			let trimmed = self.versions.values().fold(id, |id, sqids| sqids.trimmed(id));
+			return Err(trimmed.chars().next().map_or(Error::InvalidId, Error::UnknownVersion));
+		};
+
+		match sqids.try_decode(rest)? {
+			numbers if numbers.is_empty() => Err(Error::InvalidId),
+			numbers => Ok((version, numbers)),
+		}
+	}
+}
+
+fn tag(version: u8, sqids: &Sqids) -> Option<char> {
+	sqids.original_alphabet.chars().nth(version as usize)
+}
@@ -0,0 +1,66 @@
+//! JavaScript bindings, enabled with the `wasm` feature.
+//!
+//! JS numbers are doubles, so `encode` and `decode` only accept integers up to
+//! `Number.MAX_SAFE_INTEGER` (2^53 - 1) and return an error for anything else. Use
+//! `encodeBigInt` and `decodeBigInt` to work with the full `u64` range.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Options, Sqids};
+
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+#[wasm_bindgen(js_name = Sqids)]
+pub struct WasmSqids {
+	inner: Sqids,
+}
+
+#[wasm_bindgen(js_class = Sqids)]
+impl WasmSqids {
+	#[wasm_bindgen(constructor)]
+	pub fn new(alphabet: Option<String>, min_length: Option<u8>) -> Result<WasmSqids, JsError> {
+		let inner = Sqids::new(Some(Options::new(alphabet, min_length, None)))?;
+		Ok(WasmSqids { inner })
+	}
+
+	pub fn encode(&self, numbers: &[f64]) -> Result<String, JsError> {
+		let numbers = numbers.iter().map(|&n| from_js_number(n)).collect::<Result<Vec<_>, _>>()?;
+		Ok(self.inner.encode(&numbers)?)
+	}
+
+	pub fn decode(&self, id: &str) -> Result<Vec<f64>, JsError> {
+		self.inner
+			.decode(id)
+			.into_iter()
+			.map(|n| {
+				if n > MAX_SAFE_INTEGER {
+					return Err(JsError::new(
+						"Decoded number exceeds Number.MAX_SAFE_INTEGER, use decodeBigInt",
+					));
+				}
+				Ok(n as f64)
+			})
+			.collect()
+	}
+
+	#[wasm_bindgen(js_name = encodeBigInt)]
+	pub fn encode_bigint(&self, numbers: &[u64]) -> Result<String, JsError> {
+		Ok(self.inner.encode(numbers)?)
+	}
+
+	#[wasm_bindgen(js_name = decodeBigInt)]
+	pub fn decode_bigint(&self, id: &str) -> Vec<u64> {
+		self.inner.decode(id)
+	}
+}
+
+fn from_js_number(n: f64) -> Result<u64, JsError> {
+	// NaN fails the range check as well
+	if n.fract() != 0.0 || !(0.0..=MAX_SAFE_INTEGER as f64).contains(&n) {
+		return Err(JsError::new(
+			"Numbers must be integers between 0 and Number.MAX_SAFE_INTEGER, use encodeBigInt",
+		));
+	}
+
+	Ok(n as u64)
+}
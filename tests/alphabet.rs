@@ -55,3 +55,222 @@ fn too_short_alphabet() {
 		Error::AlphabetLength
 	)
 }
+
+#[test]
+fn validate_alphabet_without_constructing() {
+	assert_eq!(validate_alphabet("0123456789abcdef"), Ok(()));
+	assert_eq!(validate_alphabet("ë1092"), Err(Error::AlphabetMultibyteCharacters));
+	assert_eq!(validate_alphabet("ab"), Err(Error::AlphabetLength));
+	assert_eq!(validate_alphabet("aabcdefg"), Err(Error::AlphabetUniqueCharacters));
+}
+
+#[test]
+fn preset_default() {
+	assert_eq!(validate_alphabet(alphabet::DEFAULT), Ok(()));
+	assert_eq!(alphabet::DEFAULT, Options::default().alphabet);
+}
+
+#[test]
+fn preset_no_lookalikes() {
+	assert_eq!(validate_alphabet(alphabet::NO_LOOKALIKES), Ok(()));
+	assert_eq!(alphabet::NO_LOOKALIKES.len(), 57);
+	assert!(!alphabet::NO_LOOKALIKES.contains(['0', 'O', '1', 'l', 'I']));
+}
+
+#[test]
+fn preset_url_safe() {
+	assert_eq!(validate_alphabet(alphabet::URL_SAFE), Ok(()));
+	assert_eq!(alphabet::URL_SAFE.len(), 64);
+	assert!(alphabet::URL_SAFE.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+}
+
+#[test]
+fn preset_base58() {
+	assert_eq!(validate_alphabet(alphabet::BASE58), Ok(()));
+	assert_eq!(alphabet::BASE58.len(), 58);
+	assert!(!alphabet::BASE58.contains(['0', 'O', 'I', 'l']));
+}
+
+#[test]
+fn default_alphabet_without() {
+	let alphabet = Options::default_alphabet_without("aeiouAEIOU").unwrap();
+
+	assert_eq!(alphabet, "bcdfghjklmnpqrstvwxyzBCDFGHJKLMNPQRSTVWXYZ0123456789");
+
+	let sqids = Sqids::new(Some(Options::new(Some(alphabet), None, None))).unwrap();
+	assert_eq!(sqids.decode(&sqids.encode(&[1, 2, 3]).unwrap()), vec![1, 2, 3]);
+}
+
+#[test]
+fn default_alphabet_without_unknown_characters() {
+	assert_eq!(
+		Options::default_alphabet_without("a-").err().unwrap(),
+		Error::AlphabetExcludedCharacters
+	);
+}
+
+#[test]
+fn default_alphabet_without_too_many_characters() {
+	let exclude: String = alphabet::DEFAULT.chars().skip(2).collect();
+
+	assert_eq!(Options::default_alphabet_without(&exclude).err().unwrap(), Error::AlphabetLength);
+}
+
+#[test]
+fn alphabet_excluding() {
+	assert_eq!(Options::alphabet_excluding("abcdef", "be").unwrap(), "acdf");
+	assert_eq!(
+		Options::alphabet_excluding("abcdef", "x").err().unwrap(),
+		Error::AlphabetExcludedCharacters
+	);
+	assert_eq!(Options::alphabet_excluding("abcd", "ab").err().unwrap(), Error::AlphabetLength);
+	assert_eq!(
+		Options::alphabet_excluding("abcda", "d").err().unwrap(),
+		Error::AlphabetUniqueCharacters
+	);
+}
+
+#[test]
+fn alphabet_policy_url_safe() {
+	let options = Options {
+		alphabet: "abc+/".to_string(),
+		alphabet_policy: Some(AlphabetPolicy::URL_SAFE),
+		..Options::default()
+	};
+
+	assert_eq!(
+		Sqids::new(Some(options)).err().unwrap(),
+		Error::AlphabetPolicyViolation { policy: "URL-safe", characters: vec!['+', '/'] }
+	);
+	assert!(AlphabetPolicy::URL_SAFE.validate(alphabet::URL_SAFE).is_ok());
+}
+
+#[test]
+fn alphabet_policy_filename_safe() {
+	assert_eq!(
+		AlphabetPolicy::FILENAME_SAFE.validate("abc~").err().unwrap(),
+		Error::AlphabetPolicyViolation { policy: "filename-safe", characters: vec!['~'] }
+	);
+	assert!(AlphabetPolicy::FILENAME_SAFE.validate(alphabet::URL_SAFE).is_ok());
+}
+
+#[test]
+fn alphabet_policy_case_insensitive_safe() {
+	assert_eq!(
+		AlphabetPolicy::CASE_INSENSITIVE_SAFE.validate("abcB1").err().unwrap(),
+		Error::AlphabetPolicyViolation {
+			policy: "case-insensitive-safe",
+			characters: vec!['b', 'B']
+		}
+	);
+	assert!(AlphabetPolicy::CASE_INSENSITIVE_SAFE.validate("abc123").is_ok());
+}
+
+#[test]
+fn alphabet_policies_combined() {
+	let policy = AlphabetPolicy::URL_SAFE | AlphabetPolicy::CASE_INSENSITIVE_SAFE;
+
+	assert!(policy.contains(AlphabetPolicy::URL_SAFE));
+	assert!(!policy.contains(AlphabetPolicy::FILENAME_SAFE));
+	assert!(policy.validate("abc~").is_ok());
+	assert!(policy.validate("abcA").is_err());
+	assert!(policy.validate("abc/").is_err());
+
+	let sqids = Sqids::new(Some(Options {
+		alphabet: "abcdefghijklmnopqrstuvwxyz0123456789".to_string(),
+		alphabet_policy: Some(policy),
+		..Options::default()
+	}))
+	.unwrap();
+	assert_eq!(sqids.decode(&sqids.encode(&[1, 2, 3]).unwrap()), vec![1, 2, 3]);
+}
+
+#[test]
+fn alphabet_offset() {
+	let numbers = [1, 2, 3];
+	let ids: Vec<String> = (0..5)
+		.map(|offset| {
+			let sqids = Sqids::new(Some(Options { alphabet_offset: offset, ..Options::default() }))
+				.unwrap();
+			let id = sqids.encode(&numbers).unwrap();
+			assert_eq!(sqids.decode(&id), numbers);
+			id
+		})
+		.collect();
+
+	assert_eq!(ids[0], "86Rf07");
+	for (i, id) in ids.iter().enumerate() {
+		assert!(ids.iter().skip(i + 1).all(|other| other != id));
+	}
+}
+
+#[test]
+fn alphabet_offset_wraps_around() {
+	let sqids = Sqids::new(Some(Options {
+		alphabet_offset: alphabet::DEFAULT.len(),
+		..Options::default()
+	}))
+	.unwrap();
+
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "86Rf07");
+	assert_eq!(sqids.config_fingerprint(), Sqids::default().config_fingerprint());
+}
+
+#[test]
+fn alphabet_offset_separates_id_spaces() {
+	let a = Sqids::new(Some(Options { alphabet_offset: 1, ..Options::default() })).unwrap();
+	let b = Sqids::new(Some(Options { alphabet_offset: 2, ..Options::default() })).unwrap();
+
+	let numbers = vec![1, 2, 3];
+	assert_ne!(b.decode(&a.encode(&numbers).unwrap()), numbers);
+	assert_ne!(a.config_fingerprint(), b.config_fingerprint());
+}
+
+#[test]
+fn spec_shuffle() {
+	assert_eq!(
+		shuffle(alphabet::DEFAULT),
+		"fwjBhEY2uczNPDiloxmvISCrytaJO4d71T0W3qnMZbXVHg6eR8sAQ5KkpLUGF9"
+	);
+	assert_eq!(shuffle("0123456789"), "4086517392");
+	assert_eq!(shuffle("abcdefghijklmnopqrstuvwxyz"), "lbfziqvscptmyxrekguohwjand");
+	assert_eq!(shuffle("ABCDEFGHIJKLMNOPQRSTUVWXYZ"), "ZXBNSIJQEDMCTKOHVWFYUPLRGA");
+
+	let mut chars: Vec<char> = "0123456789".chars().collect();
+	shuffle_chars(&mut chars);
+	assert_eq!(chars.into_iter().collect::<String>(), "4086517392");
+}
+
+#[test]
+fn spec_shuffle_large_code_points() {
+	// long enough for `i * j + chars[i] + chars[j]` to overflow a u32
+	let alphabet: Vec<char> = (0x20000..0x20000 + 140_000).filter_map(char::from_u32).collect();
+
+	let mut expected = alphabet.clone();
+	for i in 0..expected.len() - 1 {
+		let j = expected.len() - 1 - i;
+		let r = (i as u128 * j as u128 + expected[i] as u128 + expected[j] as u128)
+			% expected.len() as u128;
+		expected.swap(i, r as usize);
+	}
+
+	let mut chars = alphabet.clone();
+	shuffle_chars(&mut chars);
+	assert_eq!(chars, expected);
+
+	let mut sorted = chars.clone();
+	sorted.sort();
+	assert_eq!(sorted, alphabet);
+
+	shuffle_chars(&mut []);
+}
+
+#[test]
+fn from_str() {
+	let sqids: Sqids = "0123456789abcdef".parse().unwrap();
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "489158");
+	assert_eq!(sqids.min_length(), 0);
+
+	assert_eq!("ab".parse::<Sqids>().err().unwrap(), Error::AlphabetLength);
+	assert_eq!("aab".parse::<Sqids>().err().unwrap(), Error::AlphabetUniqueCharacters);
+}
@@ -0,0 +1,42 @@
+use sqids::*;
+
+#[test]
+fn audit_range() {
+	assert_eq!(Sqids::default().audit_range(0..5000), Ok(()));
+
+	let padded = Sqids::new(Some(Options {
+		min_length: 10,
+		..Options::new(Some("abc".to_string()), None, None)
+	}))
+	.unwrap();
+	assert_eq!(padded.audit_range(0..1000), Ok(()));
+}
+
+#[test]
+fn audit_range_reports_the_first_failure() {
+	let sqids = Sqids::new(Some(Options { max_length: Some(2), ..Options::default() })).unwrap();
+	let first_long = (0..).find(|&n| sqids.encode(&[n]).is_err()).unwrap();
+
+	assert_eq!(
+		sqids.audit_range(0..first_long + 10),
+		Err(AuditReport::EncodeFailed { number: first_long, error: Error::MaxLengthExceeded })
+	);
+	assert_eq!(sqids.audit_range(0..first_long), Ok(()));
+}
+
+#[test]
+fn audit_range_reports_roundtrip_failures() {
+	// skips the check that the padding character isn't in the alphabet, so decoding strips it
+	// from IDs that happen to end in it
+	let sqids = Sqids::new_unchecked(Options {
+		padding: PaddingStrategy::RepeatChar('a'),
+		..Options::new(None, None, Some(Default::default()))
+	});
+
+	let report = sqids.audit_range(0..1000).unwrap_err();
+	let AuditReport::Roundtrip { number, id, decoded } = report else {
+		panic!("{report:?}");
+	};
+	assert!(id.ends_with('a'));
+	assert_ne!(decoded, [number]);
+}
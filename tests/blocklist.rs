@@ -1,6 +1,9 @@
+#![cfg(feature = "blocklist")]
+
 use sqids::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
+#[cfg(feature = "default-blocklist")]
 #[test]
 fn if_no_custom_blocklist_param_use_default_blocklist() {
 	let sqids = Sqids::default();
@@ -114,5 +117,566 @@ fn max_encoding_attempts() {
 	assert_eq!(min_length as usize, alphabet.len());
 	assert_eq!(min_length as usize, blocklist.len());
 
-	assert_eq!(sqids.encode(&[0]).err().unwrap(), Error::BlocklistMaxAttempts);
+	assert!(matches!(
+		sqids.encode(&[0]),
+		Err(Error::BlocklistMaxAttempts { attempts: 4, ref word }) if blocklist.contains(word)
+	));
+}
+
+#[test]
+fn max_regen_attempts() {
+	let options = Options::new(None, None, Some(HashSet::from(["aho1e".to_owned()])));
+
+	// with the default limit the blocked ID is re-generated
+	let sqids = Sqids::new(Some(options.clone())).unwrap();
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "JExTR");
+
+	let sqids = Sqids::new(Some(Options { max_regen_attempts: Some(0), ..options })).unwrap();
+	assert_eq!(
+		sqids.encode(&[4572721]).err().unwrap(),
+		Error::BlocklistMaxAttempts { attempts: 1, word: "aho1e".to_string() }
+	);
+
+	// raising the limit still fails when every candidate is blocked
+	let sqids = Sqids::new(Some(Options {
+		max_regen_attempts: Some(10),
+		..Options::new(
+			Some("abc".to_string()),
+			Some(3),
+			Some(HashSet::from(["cab".to_owned(), "abc".to_owned(), "bca".to_owned()])),
+		)
+	}))
+	.unwrap();
+	assert!(matches!(sqids.encode(&[0]), Err(Error::BlocklistMaxAttempts { attempts: 11, .. })));
+}
+
+#[test]
+fn is_blocked() {
+	let sqids = Sqids::new(Some(Options::new(
+		None,
+		None,
+		Some(HashSet::from(["pnd".to_owned(), "a1b2".to_owned(), "word".to_owned()])),
+	)))
+	.unwrap();
+
+	// short words only match exactly
+	assert!(sqids.is_blocked("PND"));
+	assert!(!sqids.is_blocked("xpnd"));
+
+	// words with digits only match as a prefix or suffix
+	assert!(sqids.is_blocked("a1b2xyz"));
+	assert!(sqids.is_blocked("xyza1b2"));
+	assert!(!sqids.is_blocked("xa1b2y"));
+
+	// other words match anywhere
+	assert!(sqids.is_blocked("xWoRdy"));
+	assert!(!sqids.is_blocked("wor"));
+}
+
+#[test]
+fn encode_with_stats() {
+	let sqids =
+		Sqids::new(Some(Options::new(None, None, Some(HashSet::from(["aho1e".to_owned()])))))
+			.unwrap();
+
+	assert_eq!(sqids.encode_with_stats(&[4572721]).unwrap(), ("JExTR".to_string(), 2));
+	assert_eq!(sqids.encode_with_stats(&[1, 2, 3]).unwrap(), ("86Rf07".to_string(), 1));
+	assert_eq!(sqids.encode_with_stats(&[]).unwrap(), (String::new(), 0));
+}
+
+fn with_mode(words: &[&str], blocklist_mode: BlocklistMode) -> Sqids {
+	let blocklist = words.iter().map(|word| word.to_string()).collect();
+	Sqids::new(Some(Options { blocklist, blocklist_mode, ..Options::default() })).unwrap()
+}
+
+#[test]
+fn blocklist_mode_off() {
+	let sqids =
+		Sqids::new(Some(Options { blocklist_mode: BlocklistMode::Off, ..Options::default() }))
+			.unwrap();
+
+	assert!(!sqids.is_blocked("aho1e"));
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "aho1e");
+}
+
+#[test]
+fn blocklist_mode_exact() {
+	let sqids = with_mode(&["word", "a1b2"], BlocklistMode::Exact);
+
+	assert!(sqids.is_blocked("word"));
+	assert!(sqids.is_blocked("WoRd"));
+	assert!(sqids.is_blocked("a1b2"));
+	assert!(!sqids.is_blocked("wordy"));
+	assert!(!sqids.is_blocked("xwordy"));
+	assert!(!sqids.is_blocked("a1b2x"));
+}
+
+#[test]
+fn blocklist_mode_prefix_suffix() {
+	let sqids = with_mode(&["word", "a1b2"], BlocklistMode::PrefixSuffix);
+
+	assert!(sqids.is_blocked("word"));
+	assert!(sqids.is_blocked("wordy"));
+	assert!(sqids.is_blocked("XWORD"));
+	assert!(sqids.is_blocked("a1b2x"));
+	assert!(!sqids.is_blocked("xwordy"));
+	assert!(!sqids.is_blocked("xa1b2y"));
+}
+
+#[test]
+fn blocklist_mode_substring() {
+	assert_eq!(Options::default().blocklist_mode, BlocklistMode::Substring);
+
+	let sqids = with_mode(&["word", "a1b2", "abc"], BlocklistMode::Substring);
+
+	assert!(sqids.is_blocked("xwordy"));
+	assert!(sqids.is_blocked("a1b2x"));
+	assert!(!sqids.is_blocked("xa1b2y"));
+	assert!(sqids.is_blocked("abc"));
+	assert!(!sqids.is_blocked("xabcy"));
+}
+
+#[test]
+fn blocklist_mode_changes_ids() {
+	// "kQAKMV" only contains "qakm" in the middle
+	let substring = with_mode(&["qakm"], BlocklistMode::Substring);
+	let prefix_suffix = with_mode(&["qakm"], BlocklistMode::PrefixSuffix);
+
+	assert_ne!(substring.encode(&[63, 63]).unwrap(), "kQAKMV");
+	assert_eq!(prefix_suffix.encode(&[63, 63]).unwrap(), "kQAKMV");
+	assert_ne!(substring.config_fingerprint(), prefix_suffix.config_fingerprint());
+}
+
+#[test]
+fn blocklist_case_sensitive() {
+	let options = |blocklist_case_sensitive| Options {
+		blocklist: HashSet::from(["QAKM".to_string()]),
+		blocklist_case_sensitive,
+		..Options::default()
+	};
+	let insensitive = Sqids::new(Some(options(false))).unwrap();
+	let sensitive = Sqids::new(Some(options(true))).unwrap();
+
+	assert!(insensitive.is_blocked("kQAKMV"));
+	assert!(insensitive.is_blocked("kqakmV"));
+	assert!(sensitive.is_blocked("kQAKMV"));
+	assert!(!sensitive.is_blocked("kqakmV"));
+
+	assert_eq!(sensitive.blocklist(), &HashSet::from(["QAKM".to_string()]));
+	assert_ne!(sensitive.encode(&[63, 63]).unwrap(), "kQAKMV");
+
+	let lowercase = Sqids::new(Some(Options {
+		blocklist: HashSet::from(["qakm".to_string()]),
+		blocklist_case_sensitive: true,
+		..Options::default()
+	}))
+	.unwrap();
+	assert_ne!(insensitive.encode(&[63, 63]).unwrap(), "kQAKMV");
+	assert_eq!(lowercase.encode(&[63, 63]).unwrap(), "kQAKMV");
+}
+
+#[test]
+fn blocklist_case_sensitive_filtering() {
+	let options = |blocklist_case_sensitive| Options {
+		alphabet: "abcdefghijklmnopqrstuvwxyz".to_string(),
+		blocklist: HashSet::from(["Word".to_string(), "word".to_string()]),
+		blocklist_case_sensitive,
+		..Options::default()
+	};
+
+	// "Word" only survives when it's lowercased to fit the alphabet
+	let insensitive = Sqids::new(Some(options(false))).unwrap();
+	let sensitive = Sqids::new(Some(options(true))).unwrap();
+
+	assert_eq!(insensitive.blocklist(), &HashSet::from(["word".to_string()]));
+	assert_eq!(sensitive.blocklist(), &HashSet::from(["word".to_string()]));
+	assert!(!sensitive.is_blocked("xWordy"));
+	assert!(insensitive.is_blocked("xWordy"));
+}
+
+fn leetspeak(blocklist_mode: BlocklistMode) -> Sqids {
+	Sqids::new(Some(Options {
+		blocklist: HashSet::from(["shit".to_string(), "rfot".to_string()]),
+		blocklist_mode,
+		blocklist_substitutions: Some(HashMap::from([
+			('1', 'i'),
+			('3', 'e'),
+			('0', 'o'),
+			('5', 's'),
+			('7', 't'),
+		])),
+		..Options::default()
+	}))
+	.unwrap()
+}
+
+#[test]
+fn blocklist_substitutions() {
+	let plain = with_mode(&["shit"], BlocklistMode::Substring);
+	let sqids = leetspeak(BlocklistMode::Substring);
+
+	assert!(!plain.is_blocked("x5H1tx"));
+	assert!(sqids.is_blocked("x5H1tx"));
+	assert!(sqids.is_blocked("xshitx"));
+	assert!(!sqids.is_blocked("x5h2tx"));
+
+	// [1, 2, 3] is "86Rf07" without a blocklist
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert_ne!(id, "86Rf07");
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+}
+
+#[test]
+fn blocklist_substitutions_respect_mode() {
+	let exact = leetspeak(BlocklistMode::Exact);
+	assert!(exact.is_blocked("5h1t"));
+	assert!(!exact.is_blocked("5h1tx"));
+
+	let prefix_suffix = leetspeak(BlocklistMode::PrefixSuffix);
+	assert!(prefix_suffix.is_blocked("5h1tx"));
+	assert!(!prefix_suffix.is_blocked("x5h1tx"));
+
+	assert!(!leetspeak(BlocklistMode::Off).is_blocked("5h1t"));
+}
+
+#[test]
+fn blocklist_substitutions_keep_words() {
+	let options = |blocklist_substitutions| Options {
+		alphabet: "abcdefghjklmnopqrtuvwxyz0123456789".to_string(),
+		blocklist: HashSet::from(["shit".to_string()]),
+		blocklist_substitutions,
+		..Options::default()
+	};
+
+	let plain = Sqids::new(Some(options(None))).unwrap();
+	let sqids = Sqids::new(Some(options(Some(HashMap::from([('5', 's'), ('1', 'i')]))))).unwrap();
+
+	assert!(plain.blocklist().is_empty());
+	assert_eq!(sqids.blocklist(), &HashSet::from(["shit".to_string()]));
+	assert!(sqids.is_blocked("x5h1tx"));
+}
+
+#[test]
+fn add_blocklist_words() {
+	let mut sqids = Sqids::new(Some(Options::new(None, None, Some(HashSet::new())))).unwrap();
+	let issued = sqids.encode(&[4572721]).unwrap();
+	assert_eq!(issued, "aho1e");
+
+	let added =
+		sqids.add_blocklist_words(["AHO1E".to_string(), "ab".to_string(), "a-b".to_string()]);
+	assert_eq!(added, 1);
+	assert_eq!(sqids.blocklist(), &HashSet::from(["aho1e".to_string()]));
+
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "JExTR");
+	assert_eq!(sqids.decode(&issued), vec![4572721]);
+}
+
+#[cfg(feature = "default-blocklist")]
+#[test]
+fn remove_blocklist_words() {
+	let mut sqids = Sqids::default();
+	let copy = sqids.clone();
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "JExTR");
+
+	let word = sqids.blocklist().iter().find(|word| "aho1e".contains(word.as_str())).cloned();
+	let removed =
+		sqids.remove_blocklist_words([word.unwrap().to_uppercase(), "notaword".to_string()]);
+	assert_eq!(removed, 1);
+
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "aho1e");
+	assert_eq!(copy.encode(&[4572721]).unwrap(), "JExTR");
+}
+
+#[test]
+fn blocklist_from_path() {
+	let blocklist = Options::blocklist_from_path(concat!(
+		env!("CARGO_MANIFEST_DIR"),
+		"/tests/fixtures/blocklist.txt"
+	))
+	.unwrap();
+
+	assert_eq!(
+		blocklist,
+		HashSet::from(["foobar".to_string(), "Kitten".to_string(), "w0rd".to_string()])
+	);
+
+	let sqids = Sqids::new(Some(Options { blocklist, ..Options::default() })).unwrap();
+	assert!(sqids.is_blocked("xkittenx"));
+
+	assert!(matches!(
+		Options::blocklist_from_path("/nonexistent/blocklist.txt").err().unwrap(),
+		Error::BlocklistFile { path, .. } if path == "/nonexistent/blocklist.txt"
+	));
+}
+
+#[test]
+fn blocklist_from_reader() {
+	let blocklist = Options::blocklist_from_reader(&b"one\r\n#two\n\nthree"[..]).unwrap();
+	assert_eq!(blocklist, HashSet::from(["one".to_string(), "three".to_string()]));
+
+	assert!(matches!(
+		Options::blocklist_from_reader(&b"one\ntwo\nth\xffree\n"[..]).err().unwrap(),
+		Error::BlocklistLine { line: 3, .. }
+	));
+}
+
+#[test]
+fn blocklist_from_path_invalid_utf8() {
+	let path = std::env::temp_dir().join("sqids-invalid-blocklist.txt");
+	std::fs::write(&path, b"one\n\xff\n").unwrap();
+
+	let err = Options::blocklist_from_path(&path).err().unwrap();
+	std::fs::remove_file(&path).unwrap();
+	assert!(matches!(err, Error::BlocklistFile { reason, .. } if reason.starts_with("line 2:")));
+}
+
+#[test]
+fn blocklists_by_language() {
+	let languages = ["de", "en", "es", "fr", "hi", "it", "pt"];
+	let mut union = HashSet::new();
+
+	for language in languages {
+		let Some(blocklist) = blocklist_for(language) else {
+			continue;
+		};
+
+		let sqids =
+			Sqids::new(Some(Options { blocklist: blocklist.clone(), ..Options::default() }))
+				.unwrap();
+		assert_eq!(sqids.blocklist(), &blocklist, "{language}");
+
+		union.extend(blocklist);
+	}
+
+	assert_eq!(union, default_blocklist());
+	assert_eq!(blocklist_for("nl"), None);
+}
+
+#[test]
+fn effective_blocklist() {
+	let blocklist = HashSet::from(["Zebra", "ab", "a-b-c", "apple", "MANGO"].map(String::from));
+	let sqids = Sqids::new(Some(Options::new(None, None, Some(blocklist)))).unwrap();
+
+	assert_eq!(sqids.blocklist_len(), 3);
+	assert_eq!(sqids.effective_blocklist().collect::<Vec<_>>(), ["apple", "mango", "zebra"]);
+}
+
+#[test]
+fn blocked_word_in() {
+	let blocklist = HashSet::from(["Kitten".to_string(), "4ever".to_string()]);
+	let sqids = Sqids::new(Some(Options::new(None, None, Some(blocklist)))).unwrap();
+
+	assert_eq!(sqids.blocked_word_in("myKITTENs"), Some("kitten"));
+	assert_eq!(sqids.blocked_word_in("4everyoung"), Some("4ever"));
+	assert_eq!(sqids.blocked_word_in("young4everyoung"), None);
+	assert_eq!(sqids.blocked_word_in("puppy"), None);
+}
+
+#[test]
+fn new_with_report() {
+	let blocklist =
+		HashSet::from(["Cafe", "dead", "beef", "ab", "zzz", "bad-f00d"].map(String::from));
+	let (sqids, report) = Sqids::new_with_report(Some(Options::new(
+		Some("0123456789abcdef".to_string()),
+		None,
+		Some(blocklist),
+	)))
+	.unwrap();
+
+	assert_eq!(sqids.blocklist_len(), 3);
+	assert_eq!(report.total, 6);
+	assert_eq!(report.too_short, ["ab"]);
+	assert_eq!(report.outside_alphabet, ["bad-f00d", "zzz"]);
+	assert_eq!(report.dropped(), 3);
+	assert_eq!(report.dropped_fraction(), 0.5);
+
+	let (_, report) = Sqids::new_with_report(None).unwrap();
+	assert_eq!(report.dropped(), 0);
+	assert_eq!(report.total, default_blocklist().len());
+
+	assert_eq!(
+		Sqids::new_with_report(Some(Options::new(Some("ab".to_string()), None, None))).err(),
+		Some(Error::AlphabetLength)
+	);
+}
+
+#[cfg(feature = "default-blocklist")]
+#[test]
+fn extra_blocklist() {
+	let plain = Sqids::new(Some(Options::new(None, None, Some(HashSet::new())))).unwrap();
+	let custom = plain.encode(&[1, 2, 3]).unwrap();
+	assert_eq!(plain.encode(&[4572721]).unwrap(), "aho1e");
+
+	let sqids = Sqids::new(Some(Options {
+		extra_blocklist: HashSet::from([custom.to_uppercase()]),
+		..Options::default()
+	}))
+	.unwrap();
+
+	assert_eq!(sqids.blocklist_len(), default_blocklist().len() + 1);
+	assert_ne!(sqids.encode(&[4572721]).unwrap(), "aho1e");
+	assert_ne!(sqids.encode(&[1, 2, 3]).unwrap(), custom);
+	assert!(sqids.options().extra_blocklist.is_empty());
+}
+
+#[test]
+fn allowlist() {
+	let blocklist = HashSet::from(["kitten".to_string(), "aho1e".to_string()]);
+	let sqids = Sqids::new(Some(Options::new(None, None, Some(blocklist.clone())))).unwrap();
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "JExTR");
+
+	let allowed = |allowlist: &[&str]| {
+		Sqids::new(Some(Options {
+			allowlist: allowlist.iter().map(|entry| entry.to_string()).collect(),
+			..Options::new(None, None, Some(blocklist.clone()))
+		}))
+		.unwrap()
+	};
+
+	// the word no longer blocks anything
+	let sqids = allowed(&["AHO1E"]);
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "aho1e");
+	assert!(!sqids.is_blocked("xaho1ex"));
+	assert!(sqids.is_blocked("xkittenx"));
+
+	// only this exact ID is let through
+	let sqids = allowed(&["mykitten"]);
+	assert!(!sqids.is_blocked("MyKitten"));
+	assert!(sqids.is_blocked("mykittens"));
+	assert_ne!(sqids.config_fingerprint(), allowed(&[]).config_fingerprint());
+}
+
+#[test]
+fn validate_blocklist_on_decode() {
+	let blocklist = HashSet::from(["aho1e".to_string()]);
+	let sqids = Sqids::new(Some(Options {
+		validate_blocklist_on_decode: true,
+		group: Some((2, '-')),
+		..Options::new(None, None, Some(blocklist))
+	}))
+	.unwrap();
+
+	let id = sqids.encode(&[4572721]).unwrap();
+	assert_eq!(sqids.try_decode(&id), Ok(vec![4572721]));
+	assert_eq!(sqids.decode_one(&id), Ok(4572721));
+
+	for crafted in ["aho1e", "ah-o1-e", "aho1-e"] {
+		assert_eq!(sqids.decode(crafted), vec![4572721]);
+		assert_eq!(sqids.try_decode(crafted), Err(Error::BlockedId), "{crafted}");
+		assert_eq!(sqids.decode_one(crafted), Err(Error::BlockedId), "{crafted}");
+	}
+}
+
+#[test]
+fn default_blocklist_is_cached() {
+	assert!(std::ptr::eq(default_blocklist_ref(), default_blocklist_ref()));
+	assert_eq!(default_blocklist_ref(), &default_blocklist());
+	assert_eq!(&Options::default().blocklist, default_blocklist_ref());
+}
+
+#[test]
+fn bundled_blocklists_are_clean() {
+	for word in default_blocklist_ref() {
+		assert!(word.len() >= 3, "{word:?}");
+		assert_eq!(word, &word.trim().to_lowercase(), "{word:?}");
+	}
+}
+
+#[cfg(feature = "default-blocklist")]
+#[test]
+fn bundled_blocklists_match_word_lists() {
+	let lists = [
+		("de", include_str!("../src/blocklist/de.txt")),
+		("en", include_str!("../src/blocklist/en.txt")),
+		("es", include_str!("../src/blocklist/es.txt")),
+		("fr", include_str!("../src/blocklist/fr.txt")),
+		("hi", include_str!("../src/blocklist/hi.txt")),
+		("it", include_str!("../src/blocklist/it.txt")),
+		("pt", include_str!("../src/blocklist/pt.txt")),
+	];
+
+	for (language, text) in lists {
+		let mut words: Vec<String> = blocklist_for(language).unwrap().into_iter().collect();
+		words.sort();
+
+		let unpacked: String = words.iter().map(|word| format!("{word}\n")).collect();
+		assert_eq!(unpacked, text, "{language}");
+	}
+}
+
+/// The blocklist rules word by word, as `Sqids` matched before it compiled the blocklist.
+fn naive_is_blocked(words: &[String], mode: BlocklistMode, id: &str) -> bool {
+	let id = id.to_lowercase();
+	let id_len = id.chars().count();
+	words.iter().any(|word| {
+		let word_len = word.chars().count();
+		if word_len > id_len {
+			return false;
+		}
+		match mode {
+			BlocklistMode::Off => false,
+			BlocklistMode::Exact => id == *word,
+			BlocklistMode::PrefixSuffix => {
+				id.starts_with(word.as_str()) || id.ends_with(word.as_str())
+			}
+			BlocklistMode::Substring => {
+				if id_len <= 3 || word_len <= 3 {
+					id == *word
+				} else if word.chars().any(|c| c.is_ascii_digit()) {
+					id.starts_with(word.as_str()) || id.ends_with(word.as_str())
+				} else {
+					id.contains(word.as_str())
+				}
+			}
+		}
+	})
+}
+
+#[test]
+fn compiled_blocklist_matches_naive_rules() {
+	let blocklist: HashSet<String> =
+		["abc", "ab1", "xyz9", "9xyz", "hello", "hell", "ell", "lo4lo", "aaaa", "aaab", "bob"]
+			.map(str::to_string)
+			.into();
+	let allowlist = HashSet::from(["hell".to_string()]);
+	let mut words: Vec<String> = blocklist.iter().cloned().collect();
+	#[cfg(feature = "default-blocklist")]
+	words.extend(default_blocklist());
+	words.retain(|word| !allowlist.contains(word));
+	words.sort();
+
+	let mut state = 0x2545f4914f6cdd1d_u64;
+	let mut next = move |bound: usize| {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		(state % bound as u64) as usize
+	};
+	let chars: Vec<char> = "abcdefghijklmnopqrstuvwxyzABLOXY0123456789".chars().collect();
+
+	for mode in [BlocklistMode::Exact, BlocklistMode::PrefixSuffix, BlocklistMode::Substring] {
+		let sqids = Sqids::new(Some(Options {
+			blocklist: blocklist.clone(),
+			extra_blocklist: words.iter().cloned().collect(),
+			allowlist: allowlist.clone(),
+			blocklist_mode: mode,
+			..Options::default()
+		}))
+		.unwrap();
+
+		for _ in 0..20_000 {
+			let mut id: String = (0..next(10)).map(|_| chars[next(chars.len())]).collect();
+			if next(2) == 0 {
+				let at = (0..=id.len()).filter(|&i| [0, id.len()].contains(&i) || next(3) == 0);
+				let at = at.last().unwrap_or(0);
+				id.insert_str(at, &words[next(words.len())]);
+			}
+
+			assert_eq!(
+				sqids.is_blocked(&id),
+				naive_is_blocked(&words, mode, &id),
+				"{mode:?} {id:?}"
+			);
+		}
+	}
 }
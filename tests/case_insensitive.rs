@@ -0,0 +1,74 @@
+use sqids::*;
+
+const CROCKFORD: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+fn case_insensitive(alphabet: &str) -> Result<Sqids> {
+	Sqids::new(Some(Options {
+		case_insensitive_decode: true,
+		..Options::new(Some(alphabet.to_string()), None, None)
+	}))
+}
+
+#[test]
+fn decodes_any_case() {
+	let sqids = case_insensitive(CROCKFORD).unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+	assert_eq!(sqids.decode(&id.to_lowercase()), vec![1, 2, 3]);
+	assert_eq!(sqids.decode_iter(&id.to_lowercase()).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+	let sqids = case_insensitive(&CROCKFORD.to_lowercase()).unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert_eq!(sqids.decode(&id.to_uppercase()), vec![1, 2, 3]);
+}
+
+#[test]
+fn case_sensitive_by_default() {
+	let sqids = Sqids::new(Some(Options::new(Some(CROCKFORD.to_string()), None, None))).unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(sqids.decode(&id.to_lowercase()), Vec::<u64>::new());
+}
+
+#[test]
+fn mixed_case_alphabet_without_pairs() {
+	let sqids = case_insensitive("abcdefghijKLMNOPQRST0123456789").unwrap();
+
+	for numbers in [vec![1, 2, 3], vec![u64::MAX], vec![0, 100, 1_000]] {
+		let id = sqids.encode(&numbers).unwrap();
+		let swapped: String = id
+			.chars()
+			.map(|c| {
+				if c.is_ascii_lowercase() {
+					c.to_ascii_uppercase()
+				} else {
+					c.to_ascii_lowercase()
+				}
+			})
+			.collect();
+		assert_eq!(sqids.decode(&swapped), numbers);
+	}
+}
+
+#[test]
+fn only_ascii_is_folded() {
+	let sqids = case_insensitive("0123456789abcdefghi").unwrap();
+	let id = (0..).map(|n| sqids.encode(&[n]).unwrap()).find(|id| id.contains('i')).unwrap();
+
+	// `İ` lowercases to `i` in some locales, but isn't folded
+	assert!(!sqids.decode(&id).is_empty());
+	assert_eq!(sqids.decode(&id.replace('i', "İ")), Vec::<u64>::new());
+}
+
+#[test]
+fn rejects_case_pairs() {
+	assert_eq!(
+		case_insensitive(alphabet::DEFAULT).err().unwrap(),
+		Error::AlphabetCaseConflict('a')
+	);
+	assert_eq!(
+		case_insensitive("abcdefghijkX0123456789x").err().unwrap(),
+		Error::AlphabetCaseConflict('x')
+	);
+}
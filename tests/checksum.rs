@@ -0,0 +1,109 @@
+use sqids::*;
+
+fn with_checksum(min_length: u8) -> Sqids {
+	Sqids::new(Some(Options { checksum: true, ..Options::new(None, Some(min_length), None) }))
+		.unwrap()
+}
+
+#[test]
+fn simple() {
+	let sqids = with_checksum(0);
+
+	let numbers = vec![1, 2, 3];
+	let id = sqids.encode(&numbers).unwrap();
+
+	assert_eq!(id.len(), Sqids::default().encode(&numbers).unwrap().len() + 1);
+	assert_eq!(sqids.estimate_length(&numbers), id.len());
+	assert_eq!(sqids.decode(&id), numbers);
+}
+
+#[test]
+fn different_inputs() {
+	for min_length in [0, 5, 10, 30] {
+		let sqids = with_checksum(min_length);
+
+		for numbers in [
+			vec![0],
+			vec![1, 2, 3],
+			vec![0, 0, 0, 1, 2, 3, 100, 1_000, 100_000, 1_000_000, u64::MAX],
+			(0..100).collect(),
+		] {
+			let id = sqids.encode(&numbers).unwrap();
+			assert!(id.len() >= min_length as usize);
+			assert_eq!(sqids.decode(&id), numbers);
+		}
+	}
+}
+
+#[test]
+fn detects_mistyped_characters() {
+	let alphabet: Vec<char> = Options::default().alphabet.chars().collect();
+	let numbers = vec![123_456_789, 42];
+	// typos in the padding are harmless, decode ignores it
+	let unpadded_len = with_checksum(0).encode(&numbers).unwrap().len();
+
+	for min_length in [0, 20] {
+		let sqids = with_checksum(min_length);
+		let id: Vec<char> = sqids.encode(&numbers).unwrap().chars().collect();
+
+		let mut typos = 0;
+		let mut undetected = 0;
+		for i in 0..unpadded_len {
+			for &c in alphabet.iter().filter(|&&c| c != id[i]) {
+				let mut typo = id.clone();
+				typo[i] = c;
+
+				typos += 1;
+				if !sqids.decode(&typo.into_iter().collect::<String>()).is_empty() {
+					undetected += 1;
+				}
+			}
+		}
+
+		assert!(undetected * 100 < typos, "{undetected} of {typos} typos went undetected");
+	}
+}
+
+#[test]
+fn checksum_is_an_alphabet_character() {
+	let sqids = Sqids::new(Some(Options {
+		checksum: true,
+		..Options::new(Some("abc".to_string()), None, None)
+	}))
+	.unwrap();
+
+	for n in 0..100 {
+		let id = sqids.encode(&[n]).unwrap();
+		assert!(id.chars().all(|c| "abc".contains(c)));
+		assert_eq!(sqids.decode(&id), vec![n]);
+	}
+}
+
+#[test]
+fn missing_check_character() {
+	let sqids = with_checksum(0);
+
+	// a plain ID without a check character
+	assert!(sqids.decode(&Sqids::default().encode(&[1]).unwrap()).is_empty());
+}
+
+#[test]
+fn decode_iter_skips_check_character() {
+	let sqids = with_checksum(0);
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(sqids.decode_iter(&id).collect::<Vec<_>>(), vec![1, 2, 3]);
+}
+
+#[test]
+fn decode_count_ignores_check_character() {
+	let sqids = with_checksum(10);
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(sqids.decode_count(&id), 3);
+}
+
+#[test]
+fn checksum_changes_config_fingerprint() {
+	assert_ne!(with_checksum(0).config_fingerprint(), Sqids::default().config_fingerprint());
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "config")]
+
+use sqids::*;
+#[cfg(feature = "blocklist")]
+use std::collections::HashSet;
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn example_config_file() {
+	let options =
+		Options::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/config/sqids.toml"))
+			.unwrap();
+
+	assert_eq!(
+		options,
+		Options {
+			max_length: Some(16),
+			..Options::new(
+				Some("0123456789abcdefghijklmnopqrstuvwxyz".to_string()),
+				Some(8),
+				Some(HashSet::from([
+					"0rgasm".to_string(),
+					"1d10t".to_string(),
+					"anus".to_string()
+				])),
+			)
+		}
+	);
+	assert!(Sqids::new(Some(options)).is_ok());
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn toml_inline_blocklist() {
+	let options = Options::from_toml_str("min_length = 4\nblocklist = [\"abc123\"]").unwrap();
+
+	assert_eq!(options, Options::new(None, Some(4), Some(HashSet::from(["abc123".to_string()]))));
+}
+
+#[test]
+fn json_missing_fields_use_defaults() {
+	assert_eq!(Options::from_json_str("{}").unwrap(), Options::default());
+	assert_eq!(
+		Options::from_json_str(r#"{"alphabet": "abc"}"#).unwrap(),
+		Options::new(Some("abc".to_string()), None, None)
+	);
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+	assert!(matches!(
+		Options::from_toml_str("min_lenght = 4").err().unwrap(),
+		Error::Config(message) if message.contains("min_lenght")
+	));
+	assert!(matches!(
+		Options::from_json_str(r#"{"min_lenght": 4}"#).err().unwrap(),
+		Error::Config(message) if message.contains("min_lenght")
+	));
+}
+
+#[test]
+fn blocklist_and_blocklist_path_conflict() {
+	assert!(matches!(
+		Options::from_toml_str("blocklist = []\nblocklist_path = \"words.txt\"").err().unwrap(),
+		Error::Config(_)
+	));
+}
+
+#[test]
+fn unsupported_extension() {
+	assert!(matches!(Options::from_path("Cargo.lock").err().unwrap(), Error::Config(_)));
+}
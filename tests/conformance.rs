@@ -0,0 +1,29 @@
+#![cfg(feature = "conformance")]
+
+use sqids::*;
+
+#[test]
+fn spec_vectors() {
+	assert_eq!(conformance::check_all(), Vec::<String>::new());
+}
+
+#[test]
+fn every_category_is_loaded() {
+	let vectors = conformance::vectors();
+
+	for category in ["encoding", "min_length", "blocklist", "invalid"] {
+		assert!(vectors.iter().any(|vector| vector.category == category));
+	}
+}
+
+#[test]
+fn mismatches_are_reported() {
+	let mut vector =
+		conformance::vectors().into_iter().find(|vector| vector.name == "simple").unwrap();
+	vector.cases[0].id = Some("86Rf08".to_string());
+
+	assert_eq!(
+		vector.check().err().unwrap(),
+		"encoding/simple: encode([1, 2, 3]) = Ok(\"86Rf07\"), expected \"86Rf08\""
+	);
+}
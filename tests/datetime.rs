@@ -0,0 +1,37 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, TimeZone, Utc};
+use sqids::*;
+
+#[test]
+fn datetime_roundtrip() {
+	let sqids = Sqids::default();
+	let dt = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+	let id = sqids.encode_datetime(dt, &[42, 7]).unwrap();
+	assert_eq!(sqids.decode(&id), vec![dt.timestamp_millis() as u64, 42, 7]);
+	assert_eq!(sqids.decode_datetime(&id), Some((dt, vec![42, 7])));
+
+	let epoch = DateTime::UNIX_EPOCH;
+	let id = sqids.encode_datetime(epoch, &[]).unwrap();
+	assert_eq!(sqids.decode_datetime(&id), Some((epoch, vec![])));
+}
+
+#[test]
+fn datetime_before_epoch() {
+	let dt = Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap();
+
+	assert_eq!(
+		Sqids::default().encode_datetime(dt, &[1]).err().unwrap(),
+		Error::TimestampBeforeEpoch
+	);
+}
+
+#[test]
+fn decode_datetime_invalid() {
+	let sqids = Sqids::default();
+
+	assert_eq!(sqids.decode_datetime(""), None);
+	// too large for a timestamp
+	assert_eq!(sqids.decode_datetime(&sqids.encode(&[u64::MAX]).unwrap()), None);
+}
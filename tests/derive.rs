@@ -0,0 +1,44 @@
+#![cfg(feature = "derive")]
+
+use sqids::*;
+
+#[derive(Sqid, Debug, PartialEq)]
+struct Ref {
+	shard: u16,
+	id: u32,
+}
+
+#[derive(Sqid, Debug, PartialEq)]
+struct Pair(u8, u64);
+
+#[test]
+fn derive_named_fields() {
+	let sqids = Sqids::default();
+	let r = Ref { shard: 3, id: 42 };
+
+	// fields are encoded in declaration order
+	let id = r.to_sqid(&sqids).unwrap();
+	assert_eq!(id, sqids.encode(&[3, 42]).unwrap());
+	assert_eq!(Ref::from_sqid(&sqids, &id), Some(r));
+}
+
+#[test]
+fn derive_tuple_struct() {
+	let sqids = Sqids::default();
+	let pair = Pair(1, u64::MAX);
+
+	let id = pair.to_sqid(&sqids).unwrap();
+	assert_eq!(Pair::from_sqid(&sqids, &id), Some(pair));
+}
+
+#[test]
+fn from_sqid_rejects_mismatches() {
+	let sqids = Sqids::default();
+
+	// wrong number count
+	assert_eq!(Ref::from_sqid(&sqids, &sqids.encode(&[1]).unwrap()), None);
+	assert_eq!(Ref::from_sqid(&sqids, &sqids.encode(&[1, 2, 3]).unwrap()), None);
+	// out of range for `u16`
+	assert_eq!(Ref::from_sqid(&sqids, &sqids.encode(&[70_000, 1]).unwrap()), None);
+	assert_eq!(Ref::from_sqid(&sqids, "*"), None);
+}
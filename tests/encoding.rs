@@ -1,4 +1,5 @@
 use sqids::*;
+use std::collections::HashSet;
 
 #[test]
 fn simple() {
@@ -118,3 +119,401 @@ fn decoding_invalid_character() {
 	let numbers: Vec<u64> = vec![];
 	assert_eq!(sqids.decode("*"), numbers);
 }
+
+#[test]
+fn estimate_length() {
+	let sqids = Sqids::default();
+
+	assert_eq!(sqids.estimate_length(&[]), 0);
+
+	for numbers in [
+		vec![0],
+		vec![1, 2, 3],
+		vec![61, 62],
+		vec![0, 0, 0, 1, 2, 3, 100, 1_000, 100_000, 1_000_000, u64::MAX],
+	] {
+		assert_eq!(sqids.estimate_length(&numbers), sqids.encode(&numbers).unwrap().len());
+	}
+}
+
+#[test]
+fn estimate_length_with_min_length() {
+	let sqids = Sqids::new(Some(Options::new(None, Some(10), None))).unwrap();
+
+	assert_eq!(sqids.estimate_length(&[1, 2, 3]), 10);
+	assert_eq!(sqids.estimate_length(&[u64::MAX, u64::MAX]), 24);
+	assert_eq!(sqids.encode(&[u64::MAX, u64::MAX]).unwrap().len(), 24);
+}
+
+#[test]
+fn verify_roundtrip() {
+	let sqids = Sqids::new(Some(Options {
+		verify_roundtrip: true,
+		..Options::new(Some("0123456789abcdef".to_string()), Some(10), None)
+	}))
+	.unwrap();
+
+	for numbers in [vec![0], vec![1, 2, 3], vec![0, 0, 0, 1, 2, 3, 100, 1_000, u64::MAX]] {
+		assert_eq!(sqids.decode(&sqids.encode(&numbers).unwrap()), numbers);
+	}
+}
+
+#[test]
+fn decoding_number_overflow() {
+	let sqids = Sqids::default();
+	let numbers: Vec<u64> = vec![];
+
+	// `u64::MAX` takes 11 characters, one more can't fit in a `u64`
+	let id = sqids.encode(&[u64::MAX]).unwrap();
+	assert_eq!(sqids.decode(&format!("{}{}", id, &id[1..2])), numbers);
+}
+
+#[test]
+fn decode_iter() {
+	let sqids = Sqids::new(Some(Options::new(None, Some(20), None))).unwrap();
+	let numbers: Vec<u64> = (0..50).map(|n| n * 1_000).collect();
+
+	let id = sqids.encode(&numbers).unwrap();
+	assert_eq!(sqids.decode_iter(&id).take(2).collect::<Vec<_>>(), vec![0, 1_000]);
+	assert_eq!(sqids.decode_iter(&id).collect::<Vec<_>>(), numbers);
+
+	// padding isn't decoded as numbers
+	let id = sqids.encode(&[1, 2]).unwrap();
+	assert_eq!(sqids.decode_iter(&id).collect::<Vec<_>>(), vec![1, 2]);
+
+	assert_eq!(sqids.decode_iter("").count(), 0);
+	assert_eq!(sqids.decode_iter("*").count(), 0);
+}
+
+#[test]
+fn decode_count() {
+	let sqids = Sqids::new(Some(Options::new(None, Some(20), None))).unwrap();
+
+	for numbers in [vec![0], vec![1, 2, 3], (0..50).collect::<Vec<u64>>()] {
+		let id = sqids.encode(&numbers).unwrap();
+		assert_eq!(sqids.decode_count(&id), numbers.len());
+	}
+
+	assert_eq!(sqids.decode_count(""), 0);
+	assert_eq!(sqids.decode_count("*"), 0);
+}
+
+#[test]
+fn encode_to_writer() {
+	let sqids = Sqids::default();
+	let mut out = Vec::new();
+
+	for numbers in [[1, 2, 3], [4, 5, 6]] {
+		sqids.encode_to_writer(&numbers, &mut out).unwrap();
+		out.push(b'\n');
+	}
+
+	let expected =
+		format!("{}\n{}\n", sqids.encode(&[1, 2, 3]).unwrap(), sqids.encode(&[4, 5, 6]).unwrap());
+	assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
+
+#[test]
+fn encode_to_writer_nested() {
+	// a writer that encodes into another writer while it is written to
+	struct Nested<'a>(&'a Sqids, Vec<u8>);
+	impl std::io::Write for Nested<'_> {
+		fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+			self.0.encode_to_writer(&[buf.len() as u64], &mut self.1)?;
+			Ok(buf.len())
+		}
+		fn flush(&mut self) -> std::io::Result<()> {
+			Ok(())
+		}
+	}
+
+	let sqids = Sqids::default();
+	let mut nested = Nested(&sqids, Vec::new());
+	sqids.encode_to_writer(&[1, 2, 3], &mut nested).unwrap();
+
+	let len = sqids.encode(&[1, 2, 3]).unwrap().len() as u64;
+	assert_eq!(String::from_utf8(nested.1).unwrap(), sqids.encode(&[len]).unwrap());
+}
+
+#[test]
+fn encode_to_writer_error() {
+	let sqids = Sqids::new(Some(Options { max_length: Some(3), ..Options::default() })).unwrap();
+	let mut out = Vec::new();
+
+	let err = sqids.encode_to_writer(&[u64::MAX], &mut out).unwrap_err();
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+	assert_eq!(err.get_ref().unwrap().downcast_ref::<Error>(), Some(&Error::MaxLengthExceeded));
+	assert!(out.is_empty());
+}
+
+#[test]
+fn decode_one() {
+	let sqids = Sqids::default();
+
+	assert_eq!(sqids.decode_one(&sqids.encode(&[42]).unwrap()), Ok(42));
+	assert_eq!(sqids.decode_one(""), Err(Error::UnexpectedArity { expected: 1, actual: 0 }));
+	assert_eq!(
+		sqids.decode_one(&sqids.encode(&[1, 2, 3]).unwrap()),
+		Err(Error::UnexpectedArity { expected: 1, actual: 3 })
+	);
+	assert_eq!(sqids.decode_one("*"), Err(Error::InvalidId));
+}
+
+#[test]
+fn encode_sorted() {
+	let sqids = Sqids::default();
+	let sorted = [1_000_000, 1_000_001, 1_000_001, 1_000_005, 1_000_100];
+
+	let id = sqids.encode_sorted(&sorted).unwrap();
+	assert_eq!(sqids.decode_sorted(&id), sorted);
+	assert!(id.len() < sqids.encode(&sorted).unwrap().len());
+
+	assert_eq!(sqids.encode_sorted(&[]).unwrap(), "");
+	assert_eq!(sqids.encode_sorted(&[2, 1]).err().unwrap(), Error::NotSorted);
+}
+
+#[test]
+fn decode_sorted_overflow() {
+	let sqids = Sqids::default();
+	let numbers: Vec<u64> = vec![];
+
+	assert_eq!(sqids.decode_sorted(&sqids.encode(&[u64::MAX, 1]).unwrap()), numbers);
+}
+
+#[cfg(not(feature = "blocklist"))]
+#[test]
+fn without_blocklist_feature() {
+	// "aho1e" is in the default blocklist, but nothing is checked
+	assert_eq!(Sqids::default().encode(&[4572721]).unwrap(), "aho1e");
+}
+
+#[test]
+fn base_conversion() {
+	let hex: Vec<char> = "0123456789abcdef".chars().collect();
+
+	assert_eq!(to_id(0, &hex), "0");
+	assert_eq!(to_id(255, &hex), "ff");
+	assert_eq!(to_id(u64::MAX, &hex), "ffffffffffffffff");
+	assert_eq!(to_number("ff", &hex), Some(255));
+	assert_eq!(to_number("ffffffffffffffff", &hex), Some(u64::MAX));
+
+	assert_eq!(to_number("10000000000000000", &hex), None);
+	assert_eq!(to_number("fg", &hex), None);
+	assert_eq!(to_number("", &hex), None);
+}
+
+#[test]
+fn encode_with_scratch() {
+	let mut scratch = EncodeScratch::new();
+
+	for sqids in [
+		Sqids::default(),
+		Sqids::new(Some(Options::new(None, Some(20), None))).unwrap(),
+		Sqids::new(Some(Options {
+			group: Some((3, '-')),
+			prefix: Some("id_".to_string()),
+			checksum: true,
+			..Options::default()
+		}))
+		.unwrap(),
+	] {
+		for numbers in [vec![], vec![0], vec![1, 2, 3], vec![4572721], vec![u64::MAX, 42]] {
+			let expected = sqids.encode(&numbers).unwrap();
+			assert_eq!(sqids.encode_with_scratch(&numbers, &mut scratch).unwrap(), expected);
+		}
+	}
+}
+
+#[test]
+fn decode_iter_size_hint() {
+	let sqids = Sqids::new(Some(Options::new(None, Some(20), None))).unwrap();
+
+	for numbers in [vec![0], vec![1, 2, 3], (0..100).collect::<Vec<_>>()] {
+		let id = sqids.encode(&numbers).unwrap();
+		let (_, upper) = sqids.decode_iter(&id).size_hint();
+		assert!(upper.unwrap() >= numbers.len());
+	}
+
+	assert_eq!(sqids.decode_iter("*").size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn disallow_empty() {
+	let sqids = Sqids::new(Some(Options { allow_empty: false, ..Options::default() })).unwrap();
+
+	assert_eq!(sqids.encode(&[]).err().unwrap(), Error::EmptyInput);
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "86Rf07");
+	assert_eq!(sqids.decode(""), Vec::<u64>::new());
+}
+
+#[test]
+fn decode_lines() {
+	let sqids = Sqids::default();
+	let text = "86Rf07\r\n\nbM\n  \n*\r\nbM\n";
+
+	assert_eq!(sqids.decode_all_lines(text), vec![vec![1, 2, 3], vec![0], vec![], vec![0]]);
+	assert_eq!(
+		sqids.decode_lines(text).collect::<Vec<_>>(),
+		vec![(1, vec![1, 2, 3]), (3, vec![0]), (5, vec![]), (6, vec![0])]
+	);
+	assert!(sqids.decode_all_lines("").is_empty());
+	assert!(sqids.decode_all_lines("\r\n\n").is_empty());
+}
+
+#[test]
+fn encode_batch_to_string() {
+	let sqids = Sqids::default();
+
+	let joined = sqids.encode_batch_to_string(&[&[1, 2, 3], &[0], &[]], ',').unwrap();
+	assert_eq!(joined, "86Rf07,bM,");
+	assert_eq!(
+		joined.split(',').map(|id| sqids.decode(id)).collect::<Vec<_>>(),
+		vec![vec![1, 2, 3], vec![0], vec![]]
+	);
+	assert_eq!(sqids.encode_batch_to_string(&[], ',').unwrap(), "");
+	assert_eq!(
+		sqids.encode_batch_to_string(&[&[1]], 'a').err().unwrap(),
+		Error::InvalidSeparator('a')
+	);
+
+	let sqids = Sqids::new(Some(Options {
+		prefix: Some("usr_".to_string()),
+		group: Some((4, '-')),
+		..Options::default()
+	}))
+	.unwrap();
+	assert_eq!(
+		sqids.encode_batch_to_string(&[&[1]], '_').err().unwrap(),
+		Error::InvalidSeparator('_')
+	);
+	assert_eq!(
+		sqids.encode_batch_to_string(&[&[1]], '-').err().unwrap(),
+		Error::InvalidSeparator('-')
+	);
+	assert!(sqids.encode_batch_to_string(&[&[1]], ' ').is_ok());
+}
+
+#[test]
+fn trim_input() {
+	let sqids = Sqids::new(Some(Options { trim_input: true, ..Options::default() })).unwrap();
+
+	assert_eq!(sqids.decode(" \t86Rf07\r\n"), vec![1, 2, 3]);
+	assert_eq!(sqids.try_decode(" 86Rf07 ").unwrap(), vec![1, 2, 3]);
+	assert_eq!(sqids.decode_one(" bM\n").unwrap(), 0);
+	assert!(sqids.decode("86R f07").is_empty());
+	assert!(sqids.decode(" \n").is_empty());
+	assert_eq!(
+		sqids.decode_one("  ").err().unwrap(),
+		Error::UnexpectedArity { expected: 1, actual: 0 }
+	);
+
+	assert!(Sqids::default().decode(" 86Rf07").is_empty());
+}
+
+#[test]
+fn trim_input_keeps_alphabet_and_prefix() {
+	let sqids = Sqids::new(Some(Options {
+		alphabet: "abcdefghij ".to_string(),
+		trim_input: true,
+		..Options::default()
+	}))
+	.unwrap();
+	let id = (0..100)
+		.map(|n| sqids.encode(&[n]).unwrap())
+		.find(|id| id.starts_with(' ') || id.ends_with(' '))
+		.unwrap();
+	assert_eq!(sqids.decode(&id), sqids.decode(&format!("\t{id}\n")));
+
+	let sqids = Sqids::new(Some(Options {
+		prefix: Some(" id_".to_string()),
+		trim_input: true,
+		..Options::default()
+	}))
+	.unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert_eq!(sqids.decode(&format!("{id}  ")), vec![1, 2, 3]);
+	assert_eq!(sqids.try_decode(&id[1..]).err().unwrap(), Error::PrefixMismatch);
+}
+
+#[test]
+fn validate_id() {
+	let sqids = Sqids::default();
+
+	assert_eq!(sqids.validate_id("86Rf07"), Ok(()));
+	assert_eq!(
+		sqids.validate_id("86R-07"),
+		Err(Error::InvalidCharacter { character: '-', position: 3 })
+	);
+	assert_eq!(
+		sqids.validate_id("86Rf07 "),
+		Err(Error::InvalidCharacter { character: ' ', position: 6 })
+	);
+	assert_eq!(sqids.validate_id("86Rf08"), Err(Error::InvalidId));
+	assert_eq!(sqids.validate_id(""), Err(Error::InvalidId));
+
+	let sqids = Sqids::new(Some(Options {
+		prefix: Some("usr_".to_string()),
+		group: Some((4, '-')),
+		..Options::default()
+	}))
+	.unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert_eq!(sqids.validate_id(&id), Ok(()));
+	assert_eq!(sqids.validate_id("org_86Rf07"), Err(Error::PrefixMismatch));
+	assert_eq!(
+		sqids.validate_id("usr_86R!f07"),
+		Err(Error::InvalidCharacter { character: '!', position: 7 })
+	);
+}
+
+#[test]
+fn decode_first() {
+	let sqids = Sqids::default();
+	let numbers: Vec<u64> = (0..100).map(|i| i * 1_000_003).collect();
+
+	for len in [1, 2, 100] {
+		let id = sqids.encode(&numbers[..len]).unwrap();
+		assert_eq!(sqids.decode_first(&id), Some(0));
+	}
+	assert_eq!(sqids.decode_first("86Rf07"), Some(1));
+	assert_eq!(sqids.decode_first(""), None);
+	assert_eq!(sqids.decode_first("86R*07"), None);
+
+	let sqids = Sqids::new(Some(Options {
+		prefix: Some("usr_".to_string()),
+		min_length: 20,
+		..Options::default()
+	}))
+	.unwrap();
+	let id = sqids.encode(&[42, 7]).unwrap();
+	assert_eq!(sqids.decode_first(&id), Some(42));
+	assert_eq!(sqids.decode_first(&id[4..]), None);
+}
+
+#[test]
+fn reserved_ids() {
+	let plain = Sqids::new(Some(Options::new(None, None, Some(HashSet::new())))).unwrap();
+	let reserved = plain.encode(&[7]).unwrap();
+	assert_eq!(reserved.len(), 2);
+
+	let sqids = Sqids::new(Some(Options {
+		reserved_ids: HashSet::from([reserved.clone(), reserved.to_uppercase()]),
+		..Options::new(None, None, Some(HashSet::new()))
+	}))
+	.unwrap();
+	let id = sqids.encode(&[7]).unwrap();
+	assert_ne!(id, reserved);
+	assert_eq!(sqids.decode(&id), vec![7]);
+	assert_eq!(sqids.decode(&reserved), vec![7]);
+
+	let sqids = Sqids::new(Some(Options {
+		reserved_ids: HashSet::from([reserved.clone()]),
+		max_regen_attempts: Some(0),
+		..Options::new(None, None, Some(HashSet::new()))
+	}))
+	.unwrap();
+	assert_eq!(
+		sqids.encode(&[7]).err().unwrap(),
+		Error::BlocklistMaxAttempts { attempts: 1, word: reserved }
+	);
+}
@@ -0,0 +1,95 @@
+use sqids::*;
+#[cfg(feature = "blocklist")]
+use std::fs;
+use std::{collections::HashSet, env};
+
+/// Sets environment variables for the lifetime of the guard. Every test uses its own prefix, so
+/// tests running in parallel never see each other's variables.
+struct EnvGuard(Vec<String>);
+
+impl EnvGuard {
+	fn set(vars: &[(&str, &str)]) -> Self {
+		for (name, value) in vars {
+			env::set_var(name, value);
+		}
+		EnvGuard(vars.iter().map(|(name, _)| name.to_string()).collect())
+	}
+}
+
+impl Drop for EnvGuard {
+	fn drop(&mut self) {
+		for name in &self.0 {
+			env::remove_var(name);
+		}
+	}
+}
+
+#[test]
+fn unset_variables_use_defaults() {
+	assert_eq!(Options::from_env_with_prefix("SQIDS_TEST_UNSET_").unwrap(), Options::default());
+}
+
+#[test]
+fn all_variables() {
+	let _guard = EnvGuard::set(&[
+		("SQIDS_TEST_ALL_ALPHABET", "0123456789abcdef"),
+		("SQIDS_TEST_ALL_MIN_LENGTH", "8"),
+		("SQIDS_TEST_ALL_BLOCKLIST", "abc123, def456,,"),
+	]);
+
+	assert_eq!(
+		Options::from_env_with_prefix("SQIDS_TEST_ALL_").unwrap(),
+		Options::new(
+			Some("0123456789abcdef".to_string()),
+			Some(8),
+			Some(HashSet::from(["abc123".to_string(), "def456".to_string()])),
+		)
+	);
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn empty_blocklist() {
+	let _guard = EnvGuard::set(&[("SQIDS_TEST_EMPTY_BLOCKLIST", "")]);
+
+	assert!(Options::from_env_with_prefix("SQIDS_TEST_EMPTY_").unwrap().blocklist.is_empty());
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn blocklist_from_file() {
+	let path = env::temp_dir().join("sqids_test_env_blocklist.txt");
+	fs::write(&path, "abc123\n\n  def456  \n").unwrap();
+	let value = format!("@{}", path.display());
+	let _guard = EnvGuard::set(&[("SQIDS_TEST_FILE_BLOCKLIST", &value)]);
+
+	assert_eq!(
+		Options::from_env_with_prefix("SQIDS_TEST_FILE_").unwrap().blocklist,
+		HashSet::from(["abc123".to_string(), "def456".to_string()])
+	);
+	fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn missing_blocklist_file() {
+	let _guard = EnvGuard::set(&[("SQIDS_TEST_MISSING_BLOCKLIST", "@/nonexistent/blocklist.txt")]);
+
+	assert!(matches!(
+		Options::from_env_with_prefix("SQIDS_TEST_MISSING_").err().unwrap(),
+		Error::BlocklistFile { path, .. } if path == "/nonexistent/blocklist.txt"
+	));
+}
+
+#[test]
+fn invalid_min_length() {
+	let _guard = EnvGuard::set(&[("SQIDS_TEST_INVALID_MIN_LENGTH", "256")]);
+
+	assert_eq!(
+		Options::from_env_with_prefix("SQIDS_TEST_INVALID_").err().unwrap(),
+		Error::EnvVar {
+			name: "SQIDS_TEST_INVALID_MIN_LENGTH".to_string(),
+			value: "256".to_string()
+		}
+	);
+}
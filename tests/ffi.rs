@@ -0,0 +1,98 @@
+#![cfg(feature = "ffi")]
+
+use sqids::ffi::*;
+use std::{
+	ffi::{c_char, CStr},
+	ptr,
+};
+
+#[test]
+fn encode_and_decode() {
+	unsafe {
+		let sqids = sqids_new(ptr::null(), 0);
+		assert!(!sqids.is_null());
+
+		let numbers = [1, 2, 3];
+		assert_eq!(sqids_encoded_length(sqids, numbers.as_ptr(), numbers.len()), 6);
+
+		let mut out = [0 as c_char; 16];
+		assert_eq!(
+			sqids_encode(sqids, numbers.as_ptr(), numbers.len(), out.as_mut_ptr(), out.len()),
+			SQIDS_OK
+		);
+		let id = CStr::from_ptr(out.as_ptr());
+		assert_eq!(id.to_str().unwrap(), "86Rf07");
+
+		let mut decoded = [0u64; 8];
+		let mut len = 0;
+		assert_eq!(
+			sqids_decode(sqids, id.as_ptr(), decoded.as_mut_ptr(), decoded.len(), &mut len),
+			SQIDS_OK
+		);
+		assert_eq!(&decoded[..len], &numbers);
+
+		sqids_free(sqids);
+	}
+}
+
+#[test]
+fn custom_alphabet_and_min_length() {
+	unsafe {
+		let sqids = sqids_new(c"0123456789abcdef".as_ptr(), 10);
+
+		let mut out = [0 as c_char; 16];
+		assert_eq!(sqids_encode(sqids, [7].as_ptr(), 1, out.as_mut_ptr(), out.len()), SQIDS_OK);
+		assert_eq!(CStr::from_ptr(out.as_ptr()).to_bytes().len(), 10);
+
+		sqids_free(sqids);
+	}
+}
+
+#[test]
+fn invalid_alphabet() {
+	unsafe {
+		assert!(sqids_new(c"ab".as_ptr(), 0).is_null());
+		assert_eq!(sqids_validate_alphabet(c"ab".as_ptr()), SQIDS_ERROR_ALPHABET_LENGTH);
+		assert_eq!(
+			sqids_validate_alphabet(c"aabc".as_ptr()),
+			SQIDS_ERROR_ALPHABET_UNIQUE_CHARACTERS
+		);
+		assert_eq!(sqids_validate_alphabet(c"abc".as_ptr()), SQIDS_OK);
+	}
+}
+
+#[test]
+fn buffers_too_small() {
+	unsafe {
+		let sqids = sqids_new(ptr::null(), 0);
+
+		// room for the ID but not for the NUL terminator
+		let mut out = [0 as c_char; 6];
+		assert_eq!(
+			sqids_encode(sqids, [1, 2, 3].as_ptr(), 3, out.as_mut_ptr(), out.len()),
+			SQIDS_ERROR_BUFFER_TOO_SMALL
+		);
+
+		let mut decoded = [0u64; 2];
+		let mut len = 0;
+		assert_eq!(
+			sqids_decode(sqids, c"86Rf07".as_ptr(), decoded.as_mut_ptr(), decoded.len(), &mut len),
+			SQIDS_ERROR_BUFFER_TOO_SMALL
+		);
+		assert_eq!(len, 3);
+
+		sqids_free(sqids);
+	}
+}
+
+#[test]
+fn null_pointers() {
+	unsafe {
+		let mut len = 0;
+		assert_eq!(
+			sqids_decode(ptr::null(), c"86Rf07".as_ptr(), ptr::null_mut(), 0, &mut len),
+			SQIDS_ERROR_NULL_POINTER
+		);
+		sqids_free(ptr::null_mut());
+	}
+}
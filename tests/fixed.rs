@@ -0,0 +1,96 @@
+#![cfg(feature = "fixed")]
+
+use sqids::*;
+use std::collections::HashSet;
+
+const HEX: [char; 16] =
+	['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f'];
+
+fn sqids(min_length: u8, blocklist: &[&str]) -> Sqids {
+	let blocklist: HashSet<String> = blocklist.iter().map(|word| word.to_string()).collect();
+	Sqids::new(Some(Options::new(Some(HEX.iter().collect()), Some(min_length), Some(blocklist))))
+		.unwrap()
+}
+
+#[test]
+fn same_ids_as_sqids() {
+	for (min_length, blocklist) in [(0, &[][..]), (12, &[][..]), (0, &["fa3b", "c0de"][..])] {
+		let fixed = SqidsN::new(HEX, min_length, blocklist).unwrap();
+		let sqids = sqids(min_length, blocklist);
+
+		for numbers in [vec![0], vec![1, 2, 3], vec![255, 4096, 7], vec![u64::MAX], vec![]] {
+			let id = sqids.encode(&numbers).unwrap();
+
+			assert_eq!(fixed.encode::<64>(&numbers).unwrap().as_str(), id);
+			assert_eq!(fixed.decode::<8>(&id).as_slice(), numbers.as_slice());
+		}
+	}
+}
+
+#[test]
+fn blocklist() {
+	// [2, 1, 2] is "163ef6" without a blocklist
+	let fixed = SqidsN::new(HEX, 0, &["3EF6"]).unwrap();
+	let id = fixed.encode::<16>(&[2, 1, 2]).unwrap();
+
+	assert_ne!(id.as_str(), "163ef6");
+	#[cfg(feature = "blocklist")]
+	assert_eq!(id.as_str(), sqids(0, &["3EF6"]).encode(&[2, 1, 2]).unwrap());
+	assert_eq!(fixed.decode::<4>(&id).as_slice(), [2, 1, 2]);
+}
+
+#[test]
+fn capacity() {
+	let fixed = SqidsN::new(HEX, 0, &[]).unwrap();
+	let id = fixed.encode::<16>(&[1, 2, 3]).unwrap();
+
+	assert_eq!(fixed.encode::<2>(&[1, 2, 3]).err().unwrap(), Error::MaxLengthExceeded);
+	assert!(fixed.decode::<2>(&id).is_empty());
+
+	let padded = SqidsN::new(HEX, 20, &[]).unwrap();
+	assert_eq!(padded.encode::<19>(&[1]).err().unwrap(), Error::MaxLengthExceeded);
+	assert_eq!(padded.encode::<20>(&[1]).unwrap().len(), 20);
+}
+
+#[test]
+fn invalid_ids() {
+	let fixed = SqidsN::new(HEX, 0, &[]).unwrap();
+
+	assert!(fixed.decode::<4>("").is_empty());
+	assert!(fixed.decode::<4>("xyz").is_empty());
+	// overflows a u64
+	assert!(fixed.decode::<4>("fffffffffffffffffffffffff").is_empty());
+}
+
+#[test]
+fn invalid_alphabets() {
+	assert_eq!(SqidsN::new(['a', 'b'], 0, &[]).err().unwrap(), Error::AlphabetLength);
+	assert_eq!(
+		SqidsN::new(['a', 'b', 'a'], 0, &[]).err().unwrap(),
+		Error::AlphabetUniqueCharacters
+	);
+	assert_eq!(
+		SqidsN::new(['a', 'b', 'ë'], 0, &[]).err().unwrap(),
+		Error::AlphabetMultibyteCharacters
+	);
+}
+
+#[test]
+fn decode_into_array() {
+	let sqids = Sqids::default();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(sqids.decode_into_array::<3>(&id).unwrap().as_slice(), [1, 2, 3]);
+	assert_eq!(sqids.decode_into_array::<8>(&id).unwrap().as_slice(), [1, 2, 3]);
+	assert_eq!(
+		sqids.decode_into_array::<2>(&id).err().unwrap(),
+		Error::CapacityExceeded { capacity: 2 }
+	);
+	assert!(sqids.decode_into_array::<2>("").unwrap().is_empty());
+	assert_eq!(sqids.decode_into_array::<2>("*").err().unwrap(), Error::InvalidId);
+
+	let checksum = Sqids::new(Some(Options { checksum: true, ..Options::default() })).unwrap();
+	let id = checksum.encode(&[1, 2, 3]).unwrap();
+	assert_eq!(checksum.decode_into_array::<3>(&id).unwrap().as_slice(), [1, 2, 3]);
+	assert_eq!(checksum.decode_into_array::<3>(&id[..id.len() - 1]).err(), Some(Error::InvalidId));
+}
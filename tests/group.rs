@@ -0,0 +1,74 @@
+use sqids::*;
+#[cfg(feature = "blocklist")]
+use std::collections::HashSet;
+
+fn grouped(size: usize, min_length: u8) -> Result<Sqids> {
+	Sqids::new(Some(Options {
+		group: Some((size, '-')),
+		..Options::new(Some("0123456789ABCDEFGHJKMNPQRSTVWXYZ".to_string()), Some(min_length), None)
+	}))
+}
+
+#[test]
+fn groups_roundtrip() {
+	let sqids = grouped(4, 12).unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(id.len(), 14);
+	assert_eq!(id.split('-').map(str::len).collect::<Vec<_>>(), vec![4, 4, 4]);
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+	assert_eq!(sqids.decode(&id.replace('-', "")), vec![1, 2, 3]);
+	assert_eq!(sqids.estimate_length(&[1, 2, 3]), id.len());
+}
+
+#[test]
+fn last_group_can_be_shorter() {
+	let sqids = grouped(4, 10).unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(id.split('-').map(str::len).collect::<Vec<_>>(), vec![4, 4, 2]);
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+	assert_eq!(sqids.estimate_length(&[1, 2, 3]), id.len());
+
+	for numbers in [vec![0], vec![u64::MAX], vec![1, 2, 3, 4, 5]] {
+		let id = sqids.encode(&numbers).unwrap();
+		assert_eq!(sqids.decode(&id), numbers);
+		assert_eq!(sqids.estimate_length(&numbers), id.len());
+	}
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn blocklist_checks_raw_id() {
+	let plain = Sqids::new(Some(Options::new(None, None, Some(HashSet::new())))).unwrap();
+	let raw = plain.encode(&[4572721]).unwrap();
+	assert_eq!(raw, "aho1e");
+
+	// grouping would split the word, but the raw ID is still blocked
+	let sqids = Sqids::new(Some(Options {
+		group: Some((2, '-')),
+		..Options::new(None, None, Some(HashSet::from(["aho1e".to_owned()])))
+	}))
+	.unwrap();
+	let id = sqids.encode(&[4572721]).unwrap();
+	assert_ne!(id.replace('-', ""), raw);
+	assert_eq!(sqids.decode(&id), vec![4572721]);
+}
+
+#[test]
+fn invalid_group() {
+	assert_eq!(grouped(0, 0).err().unwrap(), Error::InvalidGroup);
+	let options = Options { group: Some((4, 'A')), ..Options::default() };
+	assert_eq!(Sqids::new(Some(options)).err().unwrap(), Error::InvalidGroup);
+}
+
+#[test]
+fn group_changes_config_fingerprint() {
+	let fingerprint = |group| {
+		Sqids::new(Some(Options { group, ..Options::default() })).unwrap().config_fingerprint()
+	};
+
+	assert_ne!(fingerprint(Some((2, '-'))), fingerprint(None));
+	assert_ne!(fingerprint(Some((2, '-'))), fingerprint(Some((3, '-'))));
+	assert_ne!(fingerprint(Some((2, '-'))), fingerprint(Some((2, '.'))));
+}
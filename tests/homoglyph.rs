@@ -0,0 +1,59 @@
+use sqids::*;
+use std::collections::HashMap;
+
+fn no_lookalikes(homoglyph_map: Option<HashMap<char, char>>) -> Result<Sqids> {
+	Sqids::new(Some(Options {
+		homoglyph_map,
+		..Options::new(Some(alphabet::NO_LOOKALIKES.to_string()), None, None)
+	}))
+}
+
+#[test]
+fn decodes_lookalikes() {
+	let sqids = no_lookalikes(Some(default_homoglyph_map())).unwrap();
+
+	// find an ID with an `o` and an `i` to replace with their lookalikes
+	let (numbers, id) = (0..1000)
+		.map(|n| (vec![n], sqids.encode(&[n]).unwrap()))
+		.find(|(_, id)| id.contains('o') && id.contains('i'))
+		.unwrap();
+
+	assert_eq!(sqids.decode(&id.replace('o', "0").replace('i', "l")), numbers);
+	assert_eq!(sqids.decode(&id.replace('o', "O").replace('i', "1")), numbers);
+	assert_eq!(sqids.decode(&id.replace('i', "I")), numbers);
+
+	// without a map the lookalikes are invalid characters
+	let plain = no_lookalikes(None).unwrap();
+	assert_eq!(plain.decode(&id.replace('o', "0")), Vec::<u64>::new());
+}
+
+#[test]
+fn encode_is_unchanged() {
+	let mapped = no_lookalikes(Some(default_homoglyph_map())).unwrap();
+	let plain = no_lookalikes(None).unwrap();
+
+	assert_eq!(mapped.encode(&[1, 2, 3]).unwrap(), plain.encode(&[1, 2, 3]).unwrap());
+}
+
+#[test]
+fn invalid_mappings() {
+	// the default map's sources are in the default alphabet
+	assert!(matches!(
+		Sqids::new(Some(Options {
+			homoglyph_map: Some(default_homoglyph_map()),
+			..Options::default()
+		}))
+		.err()
+		.unwrap(),
+		Error::InvalidHomoglyph { .. }
+	));
+
+	assert_eq!(
+		no_lookalikes(Some(HashMap::from([('0', 'O')]))).err().unwrap(),
+		Error::InvalidHomoglyph { from: '0', to: 'O' }
+	);
+	assert_eq!(
+		no_lookalikes(Some(HashMap::from([('a', 'b')]))).err().unwrap(),
+		Error::InvalidHomoglyph { from: 'a', to: 'b' }
+	);
+}
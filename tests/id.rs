@@ -0,0 +1,36 @@
+use sqids::*;
+
+// these tests share the process-wide default, so none of them installs a custom one
+
+#[test]
+fn display_and_parse() {
+	let id = SqidId(vec![1, 2, 3]);
+
+	assert_eq!(id.to_string(), "86Rf07");
+	assert_eq!(format!("/items/{id}"), "/items/86Rf07");
+	assert_eq!("86Rf07".parse::<SqidId>().unwrap(), id);
+}
+
+#[test]
+fn parse_rejects_invalid_ids() {
+	assert_eq!("".parse::<SqidId>().err().unwrap(), Error::InvalidId);
+	assert_eq!("*".parse::<SqidId>().err().unwrap(), Error::InvalidId);
+
+	// decodes, but isn't what `Display` would produce for those numbers
+	let numbers = Sqids::default().decode("86Rf0");
+	assert!(!numbers.is_empty());
+	assert_eq!("86Rf0".parse::<SqidId>().err().unwrap(), Error::InvalidId);
+}
+
+#[test]
+fn set_default_after_use() {
+	assert_eq!(SqidId(vec![1]).try_to_string().unwrap(), Sqids::default().encode(&[1]).unwrap());
+	assert_eq!(SqidId::set_default(Sqids::default()).err().unwrap(), Error::DefaultAlreadySet);
+}
+
+#[test]
+fn default_instance_is_shared() {
+	assert!(std::ptr::eq(default_instance(), SqidId::sqids()));
+	assert_eq!(default_instance().encode(&[1, 2, 3]).unwrap(), "86Rf07");
+	assert_eq!(set_default(Sqids::default()).err().unwrap(), Error::DefaultAlreadySet);
+}
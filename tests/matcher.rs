@@ -0,0 +1,79 @@
+use sqids::*;
+
+struct NoLetterA;
+
+impl BlockedIdMatcher for NoLetterA {
+	fn is_blocked(&self, id: &str) -> bool {
+		id.contains(['a', 'A'])
+	}
+}
+
+struct Always;
+
+impl BlockedIdMatcher for Always {
+	fn is_blocked(&self, _id: &str) -> bool {
+		true
+	}
+}
+
+fn with_matcher(matcher: impl BlockedIdMatcher + 'static) -> Sqids {
+	Sqids::new(Some(Options { matcher: Some(Matcher::new(matcher)), ..Options::default() }))
+		.unwrap()
+}
+
+#[test]
+fn custom_matcher() {
+	let sqids = with_matcher(NoLetterA);
+
+	for n in 0..1000 {
+		let id = sqids.encode(&[n]).unwrap();
+		assert!(!id.contains(['a', 'A']), "{id}");
+		assert_eq!(sqids.decode(&id), vec![n]);
+	}
+}
+
+#[test]
+fn replaces_blocklist() {
+	let sqids = with_matcher(NoLetterA);
+
+	// "aho1e" is blocked by the default blocklist; "JExTR" is its first re-generated ID
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "JExTR");
+
+	let sqids = Sqids::new(Some(Options {
+		matcher: Some(Matcher::new(NoLetterA)),
+		alphabet: "abcdefghijklmnopqrstuvwxyz".to_string(),
+		..Options::default()
+	}))
+	.unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert!(!id.contains('a'));
+	#[cfg(feature = "blocklist")]
+	assert!(!sqids.is_blocked("xshitx"));
+}
+
+#[test]
+fn always_blocked() {
+	let sqids = with_matcher(Always);
+
+	assert_eq!(sqids.encode(&[1, 2, 3]).err().unwrap(), Error::MatcherMaxAttempts { attempts: 63 });
+	assert_eq!(sqids.encode(&[]).unwrap(), "");
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn extends_builtin_rules() {
+	struct Both(Sqids);
+
+	impl BlockedIdMatcher for Both {
+		fn is_blocked(&self, id: &str) -> bool {
+			self.0.is_blocked(id) || NoLetterA.is_blocked(id)
+		}
+	}
+
+	let sqids = with_matcher(Both(Sqids::default()));
+
+	assert!(sqids.is_blocked("aho1e"));
+	assert!(sqids.is_blocked("xyzA"));
+	assert!(!sqids.is_blocked("xyzB"));
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "JExTR");
+}
@@ -0,0 +1,64 @@
+use sqids::*;
+
+#[test]
+fn within_max_length() {
+	let sqids = Sqids::new(Some(Options { max_length: Some(6), ..Options::default() })).unwrap();
+
+	let numbers = vec![1, 2, 3];
+	let id = "86Rf07";
+
+	assert_eq!(sqids.encode(&numbers).unwrap(), id);
+	assert_eq!(sqids.decode(id), numbers);
+}
+
+#[test]
+fn exceeding_max_length() {
+	let sqids = Sqids::new(Some(Options { max_length: Some(5), ..Options::default() })).unwrap();
+
+	assert_eq!(sqids.encode(&[1, 2, 3]).err().unwrap(), Error::MaxLengthExceeded);
+}
+
+#[test]
+fn max_length_counts_padding() {
+	let sqids =
+		Sqids::new(Some(Options { min_length: 10, max_length: Some(10), ..Options::default() }))
+			.unwrap();
+
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "86Rf07xd4z");
+	assert_eq!(sqids.encode(&[u64::MAX]).err().unwrap(), Error::MaxLengthExceeded);
+}
+
+#[test]
+fn max_length_below_min_length() {
+	assert_eq!(
+		Sqids::new(Some(Options { min_length: 10, max_length: Some(9), ..Options::default() }))
+			.err()
+			.unwrap(),
+		Error::MaxLengthBelowMinLength
+	);
+}
+
+#[test]
+fn fixed_length() {
+	let sqids = Sqids::new(Some(Options { fixed_length: Some(12), ..Options::default() })).unwrap();
+
+	for numbers in [vec![0], vec![1, 2, 3], vec![u64::MAX], vec![100, 200]] {
+		let id = sqids.encode(&numbers).unwrap();
+		assert_eq!(id.len(), 12);
+		assert_eq!(sqids.estimate_length(&numbers), 12);
+		assert_eq!(sqids.decode(&id), numbers);
+	}
+
+	assert_eq!(sqids.encode(&[u64::MAX, u64::MAX]).err().unwrap(), Error::MaxLengthExceeded);
+	assert_eq!(sqids.encode_with_min_length(&[1], 13).err().unwrap(), Error::MaxLengthExceeded);
+}
+
+#[test]
+fn fixed_length_out_of_range() {
+	for options in [
+		Options { min_length: 10, fixed_length: Some(9), ..Options::default() },
+		Options { max_length: Some(9), fixed_length: Some(10), ..Options::default() },
+	] {
+		assert_eq!(Sqids::new(Some(options)).err().unwrap(), Error::FixedLengthOutOfRange);
+	}
+}
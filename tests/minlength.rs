@@ -19,7 +19,7 @@ fn incremental() {
 	let alphabet_length = Options::default().alphabet.len() as u8;
 
 	let map = vec![
-		(6 as u8, "86Rf07".to_owned()),
+		(6_u8, "86Rf07".to_owned()),
 		(7, "86Rf07x".to_owned()),
 		(8, "86Rf07xd".to_owned()),
 		(9, "86Rf07xd4".to_owned()),
@@ -28,7 +28,7 @@ fn incremental() {
 		(12, "86Rf07xd4zBm".to_owned()),
 		(13, "86Rf07xd4zBmi".to_owned()),
 		(
-			alphabet_length + 0,
+			alphabet_length,
 			"86Rf07xd4zBmiJXQG6otHEbew02c3PWsUOLZxADhCpKj7aVFv9I8RquYrNlSTM".to_owned(),
 		),
 		(
@@ -93,9 +93,76 @@ fn min_lengths() {
 		] {
 			let sqids = Sqids::new(Some(Options::new(None, Some(min_length), None))).unwrap();
 
-			let id = sqids.encode(&numbers).unwrap();
+			let id = sqids.encode(numbers).unwrap();
 			assert!(id.len() >= min_length as usize);
 			assert_eq!(sqids.decode(&id), *numbers);
 		}
 	}
 }
+
+#[test]
+fn encode_with_min_length() {
+	let sqids = Sqids::default();
+	let padded = Sqids::new(Some(Options::new(None, Some(20), None))).unwrap();
+
+	assert_eq!(sqids.encode_with_min_length(&[1, 2, 3], 20), padded.encode(&[1, 2, 3]));
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "86Rf07");
+
+	// beyond what `Options::min_length` can hold
+	let id = sqids.encode_with_min_length(&[1, 2, 3], 300).unwrap();
+	assert_eq!(id.len(), 300);
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+}
+
+fn with_padding(padding: PaddingStrategy, min_length: u8) -> Result<Sqids> {
+	Sqids::new(Some(Options { padding, ..Options::new(None, Some(min_length), None) }))
+}
+
+#[test]
+fn padding_strategies() {
+	// pinned: padding must never change between releases
+	let spec = with_padding(PaddingStrategy::SpecDefault, 12).unwrap();
+	assert_eq!(spec.encode(&[1, 2, 3]).unwrap(), "86Rf07xd4zBm");
+
+	let repeat = with_padding(PaddingStrategy::RepeatChar('-'), 12).unwrap();
+	assert_eq!(repeat.encode(&[1, 2, 3]).unwrap(), "86Rf07x-----");
+	assert_eq!(repeat.encode(&[0]).unwrap(), "bMZ---------");
+
+	for numbers in [vec![0], vec![1, 2, 3], vec![u64::MAX]] {
+		let id = repeat.encode(&numbers).unwrap();
+		assert!(id.len() >= 12);
+		assert_eq!(repeat.decode(&id), numbers);
+	}
+
+	// no padding needed
+	assert_eq!(
+		with_padding(PaddingStrategy::RepeatChar('-'), 6).unwrap().encode(&[1, 2, 3]).unwrap(),
+		"86Rf07"
+	);
+}
+
+#[test]
+fn repeat_char_padding_with_checksum() {
+	let sqids = Sqids::new(Some(Options {
+		padding: PaddingStrategy::RepeatChar('.'),
+		checksum: true,
+		..Options::new(None, Some(16), None)
+	}))
+	.unwrap();
+
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert!(id.ends_with('.'));
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+}
+
+#[test]
+fn invalid_padding_char() {
+	assert_eq!(
+		with_padding(PaddingStrategy::RepeatChar('a'), 10).err().unwrap(),
+		Error::InvalidPaddingChar
+	);
+	assert_eq!(
+		with_padding(PaddingStrategy::RepeatChar('·'), 10).err().unwrap(),
+		Error::InvalidPaddingChar
+	);
+}
@@ -0,0 +1,104 @@
+use sqids::*;
+use std::collections::HashSet;
+
+const EMOJI: &str = "😀😃😄😁😆😅🤣😂🙂🙃😉😊😇🥰😍🤩😘😗😚😙";
+const MIXED: &str = "abcdefабвгдежαβγδε😀😃😄";
+
+fn multibyte(alphabet: &str, min_length: u8) -> Result<Sqids> {
+	Sqids::new(Some(Options {
+		allow_multibyte: true,
+		..Options::new(Some(alphabet.to_string()), Some(min_length), Some(HashSet::new()))
+	}))
+}
+
+#[test]
+fn rejected_by_default() {
+	assert_eq!(
+		Sqids::new(Some(Options::new(Some(EMOJI.to_string()), None, None))).err().unwrap(),
+		Error::AlphabetMultibyteCharacters
+	);
+}
+
+#[test]
+fn roundtrip() {
+	for alphabet in [EMOJI, MIXED] {
+		for min_length in [0, 10, 40] {
+			let sqids = multibyte(alphabet, min_length).unwrap();
+
+			for numbers in [vec![0], vec![1, 2, 3], vec![100, 200, 300], vec![u64::MAX, 0]] {
+				let id = sqids.encode(&numbers).unwrap();
+
+				assert!(id.chars().all(|c| alphabet.contains(c)));
+				assert!(id.chars().count() >= min_length as usize);
+				assert_eq!(sqids.estimate_length(&numbers), id.chars().count());
+				assert_eq!(sqids.decode(&id), numbers);
+			}
+		}
+	}
+}
+
+#[test]
+fn min_length_counts_characters() {
+	let sqids = multibyte(EMOJI, 10).unwrap();
+	let id = sqids.encode(&[1]).unwrap();
+
+	assert_eq!(id.chars().count(), 10);
+	assert_eq!(id.len(), 40);
+}
+
+#[test]
+fn max_length_counts_characters() {
+	let sqids = Sqids::new(Some(Options {
+		max_length: Some(10),
+		allow_multibyte: true,
+		..Options::new(Some(EMOJI.to_string()), Some(10), None)
+	}))
+	.unwrap();
+
+	assert_eq!(sqids.encode(&[1]).unwrap().chars().count(), 10);
+}
+
+#[test]
+fn checksum() {
+	let sqids = Sqids::new(Some(Options {
+		checksum: true,
+		allow_multibyte: true,
+		..Options::new(Some(MIXED.to_string()), None, None)
+	}))
+	.unwrap();
+
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn blocklist_matches_by_characters() {
+	let plain = multibyte(MIXED, 0).unwrap();
+	let id = plain.encode(&[1, 2, 3]).unwrap();
+	let word: String = id.chars().take(4).collect::<String>().to_uppercase();
+
+	let sqids = Sqids::new(Some(Options {
+		allow_multibyte: true,
+		..Options::new(Some(MIXED.to_string()), None, Some(HashSet::from([word])))
+	}))
+	.unwrap();
+
+	assert!(sqids.is_blocked(&id));
+	let reencoded = sqids.encode(&[1, 2, 3]).unwrap();
+	assert_ne!(reencoded, id);
+	assert_eq!(sqids.decode(&reencoded), vec![1, 2, 3]);
+}
+
+#[test]
+fn long_alphabet() {
+	let alphabet: String = ('\u{4e00}'..='\u{4fff}').collect();
+	let sqids = multibyte(&alphabet, 30).unwrap();
+
+	for numbers in [vec![0], vec![1, 2, 3], vec![u64::MAX, 0, 42]] {
+		let id = sqids.encode(&numbers).unwrap();
+
+		assert_eq!(id.chars().count(), 30);
+		assert_eq!(sqids.decode(&id), numbers);
+	}
+}
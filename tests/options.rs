@@ -0,0 +1,235 @@
+use sqids::*;
+use std::collections::HashSet;
+
+#[test]
+fn clone_and_compare() {
+	let options = Options::new(Some("0123456789abcdef".to_string()), Some(8), None);
+	let mut cloned = options.clone();
+
+	assert_eq!(options, cloned);
+
+	cloned.min_length = 9;
+	assert_ne!(options, cloned);
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn debug_redacts_blocklist() {
+	let options =
+		Options::new(None, None, Some(HashSet::from(["word1".to_string(), "word2".to_string()])));
+	let debug = format!("{:?}", options);
+
+	assert!(debug.contains("<2 words>"));
+	assert!(!debug.contains("word1"));
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn accessors() {
+	let sqids = Sqids::new(Some(Options {
+		max_length: Some(12),
+		..Options::new(
+			Some("0123456789abcdef".to_string()),
+			Some(8),
+			Some(HashSet::from(["ABC123".to_string(), "xyz".to_string(), "ab".to_string()])),
+		)
+	}))
+	.unwrap();
+
+	assert_eq!(sqids.alphabet(), "0123456789abcdef");
+	assert_eq!(sqids.min_length(), 8);
+	assert_eq!(sqids.max_length(), Some(12));
+	assert_eq!(sqids.blocklist(), &HashSet::from(["abc123".to_string()]));
+}
+
+#[test]
+fn options_rebuild_equivalent_sqids() {
+	let sqids = Sqids::new(Some(Options::new(Some("0123456789abcdef".to_string()), Some(8), None)))
+		.unwrap();
+	let rebuilt = Sqids::new(Some(sqids.options())).unwrap();
+
+	assert_eq!(rebuilt.options(), sqids.options());
+	assert_eq!(rebuilt.encode(&[1, 2, 3]).unwrap(), sqids.encode(&[1, 2, 3]).unwrap());
+}
+
+#[test]
+fn with_min_length_matches_rebuilt() {
+	let sqids = Sqids::default();
+	let padded = sqids.with_min_length(10).unwrap();
+	let rebuilt = Sqids::new(Some(Options { min_length: 10, ..sqids.options() })).unwrap();
+
+	assert_eq!(padded.encode(&[1, 2, 3]).unwrap(), rebuilt.encode(&[1, 2, 3]).unwrap());
+	assert_eq!(padded.options(), rebuilt.options());
+	assert_eq!(sqids.min_length(), 0);
+
+	let limited = Sqids::new(Some(Options { max_length: Some(8), ..Options::default() })).unwrap();
+	assert_eq!(limited.with_min_length(9).err().unwrap(), Error::MaxLengthBelowMinLength);
+
+	let fixed = Sqids::new(Some(Options { fixed_length: Some(8), ..Options::default() })).unwrap();
+	assert_eq!(fixed.with_min_length(9).err().unwrap(), Error::FixedLengthOutOfRange);
+	assert_eq!(fixed.with_min_length(8).unwrap().encode(&[1]).unwrap().len(), 8);
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn with_blocklist_matches_rebuilt() {
+	let blocklist = HashSet::from(["AHO1E".to_string(), "ab".to_string()]);
+	let sqids = Sqids::new(Some(Options::new(None, None, Some(HashSet::new())))).unwrap();
+	let blocked = sqids.with_blocklist(blocklist.clone()).unwrap();
+	let rebuilt = Sqids::new(Some(Options::new(None, None, Some(blocklist)))).unwrap();
+
+	assert_eq!(blocked.blocklist(), rebuilt.blocklist());
+	assert_eq!(blocked.encode(&[4572721]).unwrap(), rebuilt.encode(&[4572721]).unwrap());
+	assert_eq!(sqids.encode(&[4572721]).unwrap(), "aho1e");
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn sqids_debug_is_compact() {
+	let debug = format!("{:?}", Sqids::default());
+
+	assert!(debug.contains("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"));
+	assert!(debug.contains(" words>"));
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn config_fingerprint_is_stable() {
+	let sqids =
+		Sqids::new(Some(Options::new(None, Some(4), Some(HashSet::from(["word".to_string()])))))
+			.unwrap();
+
+	// pinned: the fingerprint format is part of the public contract
+	assert_eq!(sqids.config_fingerprint(), 0xf060ca1b32c6e54a);
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn config_fingerprint_compares_effective_config() {
+	let sqids =
+		Sqids::new(Some(Options::new(None, None, Some(HashSet::from(["word".to_string()])))))
+			.unwrap();
+
+	// blocklist words are compared after normalization
+	let same = Sqids::new(Some(Options::new(
+		None,
+		None,
+		Some(HashSet::from(["WORD".to_string(), "ab".to_string()])),
+	)))
+	.unwrap();
+	assert_eq!(same.config_fingerprint(), sqids.config_fingerprint());
+
+	let other_alphabet =
+		Sqids::new(Some(Options::new(Some("0123456789abcdef".to_string()), None, None))).unwrap();
+	let other_min_length =
+		Sqids::new(Some(Options::new(None, Some(1), Some(HashSet::from(["word".to_string()])))))
+			.unwrap();
+	assert_ne!(other_alphabet.config_fingerprint(), sqids.config_fingerprint());
+	assert_ne!(other_min_length.config_fingerprint(), sqids.config_fingerprint());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_roundtrip() {
+	let options = Options::new(
+		Some("0123456789abcdef".to_string()),
+		Some(8),
+		Some(HashSet::from(["abc123".to_string()])),
+	);
+
+	let json = serde_json::to_string(&options).unwrap();
+	assert_eq!(serde_json::from_str::<Options>(&json).unwrap(), options);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_missing_fields_use_defaults() {
+	let options: Options = serde_json::from_str(r#"{"min_length": 10}"#).unwrap();
+
+	assert_eq!(options, Options::new(None, Some(10), None));
+}
+
+#[cfg(feature = "serde")]
+#[cfg(feature = "blocklist")]
+#[test]
+fn serde_blocklist_as_list() {
+	let options: Options = serde_json::from_str(r#"{"blocklist": ["86Rf07"]}"#).unwrap();
+
+	assert_eq!(options.blocklist, HashSet::from(["86Rf07".to_string()]));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_builds_same_sqids() {
+	let programmatic =
+		Sqids::new(Some(Options::new(Some("0123456789abcdef".to_string()), Some(8), None)))
+			.unwrap();
+	let deserialized = Sqids::new(Some(
+		serde_json::from_str(r#"{"alphabet": "0123456789abcdef", "min_length": 8}"#).unwrap(),
+	))
+	.unwrap();
+
+	let numbers = [1, 2, 3];
+	assert_eq!(deserialized.encode(&numbers).unwrap(), programmatic.encode(&numbers).unwrap());
+}
+
+#[test]
+fn new_unchecked_matches_new() {
+	let options = Options::new(None, Some(8), Some(HashSet::from(["AHO1E".to_string()])));
+	let sqids = Sqids::new(Some(options)).unwrap();
+	let unchecked = Sqids::new_unchecked(sqids.options());
+
+	assert_eq!(unchecked.options(), sqids.options());
+	assert_eq!(unchecked.encode(&[4572721]).unwrap(), sqids.encode(&[4572721]).unwrap());
+}
+
+#[test]
+#[allow(clippy::mutable_key_type)] // the regex caches don't affect the hash
+fn sqids_as_hash_key() {
+	use std::collections::HashMap;
+
+	let hex = || Sqids::new(Some(Options::new(Some("0123456789abcdef".to_string()), None, None)));
+	let mut cache = HashMap::new();
+	cache.insert(Sqids::default(), "default");
+	cache.insert(hex().unwrap(), "hex");
+
+	assert_eq!(cache.get(&Sqids::default()), Some(&"default"));
+	assert_eq!(cache.get(&hex().unwrap()), Some(&"hex"));
+	assert_eq!(Sqids::default(), Sqids::new(Some(Sqids::default().options())).unwrap());
+
+	let padded = Sqids::new(Some(Options { min_length: 8, ..Options::default() })).unwrap();
+	assert_ne!(padded, Sqids::default());
+	assert_eq!(cache.get(&padded), None);
+	let seeded = Sqids::new(Some(Options { seed: Some(1), ..Options::default() })).unwrap();
+	assert_eq!(cache.get(&seeded), None);
+}
+
+#[test]
+fn hash_covers_id_options() {
+	use std::hash::{BuildHasher, RandomState};
+
+	let state = RandomState::new();
+	let hash = |options: Options| state.hash_one(Sqids::new(Some(options)).unwrap());
+	let default = hash(Options::default());
+
+	assert_eq!(hash(Options::default()), default);
+	for options in [
+		Options { prefix: Some("usr_".to_string()), ..Options::default() },
+		Options { checksum: true, ..Options::default() },
+		Options { fixed_length: Some(10), ..Options::default() },
+		Options { padding: PaddingStrategy::RepeatChar('-'), ..Options::default() },
+		Options { seed: Some(1), ..Options::default() },
+	] {
+		assert_ne!(hash(options.clone()), default, "{options:?}");
+	}
+}
+
+#[test]
+fn try_from_options() {
+	let options = Options::new(Some("0123456789abcdef".to_string()), Some(8), None);
+	let sqids = Sqids::try_from(options.clone()).unwrap();
+	assert_eq!(sqids, Sqids::new(Some(options)).unwrap());
+
+	let invalid = Options::new(Some("ab".to_string()), None, None);
+	assert_eq!(Sqids::try_from(invalid).err().unwrap(), Error::AlphabetLength);
+}
@@ -0,0 +1,58 @@
+use sqids::*;
+
+#[test]
+fn roundtrip() {
+	let sqids = Sqids::default();
+
+	for numbers in [
+		vec![],
+		vec![0],
+		vec![0, 0, 0],
+		vec![5, 0],
+		vec![1, 2, 3],
+		vec![127, 128, 16383, 16384],
+		vec![u64::MAX],
+		vec![u64::MAX, 0, u64::MAX, 1],
+		(0..100).collect(),
+		(0..40).map(|i| 1 << i).collect(),
+	] {
+		let id = sqids.encode_packed(&numbers).unwrap();
+		assert_eq!(sqids.decode_packed(&id).unwrap(), numbers, "{id}");
+	}
+}
+
+#[test]
+fn shorter_for_small_numbers() {
+	let sqids = Sqids::default();
+	let numbers = [3, 1, 0, 7, 2, 12, 5, 1, 1, 9, 4, 100000];
+
+	let packed = sqids.encode_packed(&numbers).unwrap();
+	assert!(packed.len() < sqids.encode(&numbers).unwrap().len());
+	assert_eq!(sqids.encode_packed(&[1, 2, 3]).unwrap().len(), 4);
+}
+
+#[test]
+fn format() {
+	let sqids = Sqids::default();
+
+	// 0x01 0x02 0x03 read as little-endian
+	assert_eq!(sqids.decode(&sqids.encode_packed(&[1, 2, 3]).unwrap()), vec![0x030201]);
+	// 300 is 0xac 0x02, 0 is 0x00 and needs the 0x80 marker
+	assert_eq!(sqids.decode(&sqids.encode_packed(&[300, 0]).unwrap()), vec![0x800002ac]);
+	// nine bytes take two numbers
+	assert_eq!(sqids.decode(&sqids.encode_packed(&[1; 9]).unwrap()), vec![0x0101010101010101, 1]);
+}
+
+#[test]
+fn invalid() {
+	let sqids = Sqids::default();
+	let invalid = |numbers: &[u64]| sqids.decode_packed(&sqids.encode(numbers).unwrap());
+
+	// an unterminated number, a last chunk of zero bytes, a non-canonical 0
+	assert_eq!(invalid(&[0x80]), Err(Error::InvalidId));
+	assert_eq!(invalid(&[1, 0]), Err(Error::InvalidId));
+	assert_eq!(invalid(&[0x0080]), Err(Error::InvalidId));
+	// more than 64 bits
+	assert_eq!(invalid(&[u64::MAX, 0x7fff]), Err(Error::InvalidId));
+	assert_eq!(sqids.decode_packed("*"), Err(Error::InvalidId));
+}
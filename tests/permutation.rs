@@ -0,0 +1,75 @@
+use sqids::*;
+
+/// Rotates left by `len / 3 + 1`, then swaps neighbouring pairs.
+struct RotateSwap;
+
+impl AlphabetPermutation for RotateSwap {
+	fn shuffle(&self, alphabet: &mut [char]) {
+		alphabet.rotate_left(alphabet.len() / 3 + 1);
+		for pair in alphabet.chunks_mut(2) {
+			pair.reverse();
+		}
+	}
+}
+
+struct Duplicate;
+
+impl AlphabetPermutation for Duplicate {
+	fn shuffle(&self, alphabet: &mut [char]) {
+		alphabet[0] = alphabet[1];
+	}
+}
+
+fn with_permutation(permutation: Permutation, options: Options) -> Result<Sqids> {
+	Sqids::new(Some(Options { permutation: Some(permutation), ..options }))
+}
+
+#[test]
+fn custom_permutation_roundtrips() {
+	let permutation = Permutation::new(RotateSwap);
+
+	for options in [
+		Options::default(),
+		Options::new(None, Some(30), None),
+		Options { checksum: true, ..Options::default() },
+		Options::new(Some("0123456789abcdef".to_string()), Some(8), None),
+	] {
+		let sqids = with_permutation(permutation.clone(), options.clone()).unwrap();
+		let spec = Sqids::new(Some(options)).unwrap();
+
+		for numbers in [vec![0], vec![1, 2, 3], vec![u64::MAX, 0, 42], (0..20).collect()] {
+			let id = sqids.encode(&numbers).unwrap();
+			assert_eq!(sqids.decode(&id), numbers);
+			assert_ne!(id, spec.encode(&numbers).unwrap());
+		}
+	}
+}
+
+#[test]
+fn spec_shuffle_is_the_default() {
+	let sqids = with_permutation(Permutation::new(SpecShuffle), Options::new(None, Some(10), None))
+		.unwrap();
+
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "86Rf07xd4z");
+}
+
+#[test]
+fn invalid_permutation() {
+	assert_eq!(
+		with_permutation(Permutation::new(Duplicate), Options::default()).err().unwrap(),
+		Error::InvalidPermutation
+	);
+}
+
+#[test]
+fn permutations_compare_by_instance() {
+	let permutation = Permutation::new(RotateSwap);
+	let options = Options { permutation: Some(permutation.clone()), ..Options::default() };
+
+	assert_eq!(options, Options { permutation: Some(permutation), ..Options::default() });
+	assert_ne!(
+		options,
+		Options { permutation: Some(Permutation::new(RotateSwap)), ..Options::default() }
+	);
+	assert_eq!(Sqids::new(Some(options.clone())).unwrap().options(), options);
+}
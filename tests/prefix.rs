@@ -0,0 +1,157 @@
+use sqids::*;
+
+fn with_prefix(prefix: &str, min_length: u8) -> Result<Sqids> {
+	Sqids::new(Some(Options {
+		prefix: Some(prefix.to_string()),
+		..Options::new(None, Some(min_length), None)
+	}))
+}
+
+#[test]
+fn prefix_roundtrip() {
+	let sqids = with_prefix("usr_", 0).unwrap();
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(id, format!("usr_{}", Sqids::default().encode(&[1, 2, 3]).unwrap()));
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+	assert_eq!(sqids.try_decode(&id), Ok(vec![1, 2, 3]));
+	assert_eq!(sqids.estimate_length(&[1, 2, 3]), id.len());
+}
+
+#[test]
+fn min_length_excludes_prefix() {
+	let sqids = with_prefix("ord_", 10).unwrap();
+	let id = sqids.encode(&[1]).unwrap();
+
+	assert_eq!(id.len(), "ord_".len() + 10);
+	assert_eq!(sqids.decode(&id), vec![1]);
+}
+
+#[test]
+fn wrong_prefix() {
+	let sqids = with_prefix("usr_", 0).unwrap();
+	let id = Sqids::default().encode(&[1, 2, 3]).unwrap();
+
+	assert_eq!(sqids.decode(&id), Vec::<u64>::new());
+	assert_eq!(sqids.try_decode(&id), Err(Error::PrefixMismatch));
+	assert_eq!(sqids.try_decode(&format!("USR_{id}")), Err(Error::PrefixMismatch));
+	assert_eq!(sqids.try_decode(&format!("usr_usr_{id}")), Err(Error::InvalidId));
+	assert_eq!(sqids.try_decode(""), Ok(vec![]));
+
+	let id = Sqids::default().encode(&[1]).unwrap();
+	assert_eq!(sqids.decode_one(&id), Err(Error::PrefixMismatch));
+	assert_eq!(sqids.decode_one(&format!("usr_{id}")), Ok(1));
+}
+
+#[test]
+fn invalid_prefix() {
+	// must end with a character outside of the alphabet
+	assert_eq!(with_prefix("usr", 0).err().unwrap(), Error::InvalidPrefix);
+	assert_eq!(with_prefix("", 0).err().unwrap(), Error::InvalidPrefix);
+	assert!(with_prefix("usr-", 0).is_ok());
+}
+
+fn registry() -> SqidsRegistry {
+	let mut registry = SqidsRegistry::new();
+	registry.register("usr_", Sqids::default()).unwrap();
+	registry
+		.register("ord_", Sqids::new(Some(Options::new(None, Some(8), None))).unwrap())
+		.unwrap();
+	registry
+}
+
+#[test]
+fn registry_dispatches_by_prefix() {
+	let registry = registry();
+
+	let user = registry.encode("usr_", &[1]).unwrap();
+	let order = registry.encode("ord_", &[1]).unwrap();
+	assert_eq!(order.len(), "ord_".len() + 8);
+	assert_eq!(registry.decode(&user), Ok(("usr_".to_string(), vec![1])));
+	assert_eq!(registry.decode(&order), Ok(("ord_".to_string(), vec![1])));
+
+	assert_eq!(registry.prefixes().collect::<Vec<_>>(), vec!["ord_", "usr_"]);
+	assert_eq!(registry.encode("inv_", &[1]), Err(Error::UnknownPrefix("inv_".to_string())));
+	assert_eq!(registry.decode("inv_86Rf07"), Err(Error::PrefixMismatch));
+	assert_eq!(registry.decode("usr_*"), Err(Error::InvalidId));
+}
+
+#[test]
+fn registry_longest_prefix() {
+	let mut registry = registry();
+	// `-` isn't in the default alphabet, so no `usr_` ID can start with `usr_-`
+	registry
+		.register("usr_-", Sqids::new(Some(Options::new(None, Some(6), None))).unwrap())
+		.unwrap();
+
+	let id = registry.encode("usr_-", &[2]).unwrap();
+	assert_eq!(registry.decode(&id), Ok(("usr_-".to_string(), vec![2])));
+	let id = registry.encode("usr_", &[2]).unwrap();
+	assert_eq!(registry.decode(&id), Ok(("usr_".to_string(), vec![2])));
+}
+
+#[test]
+fn registry_rejects_ambiguous_prefixes() {
+	let mut registry = registry();
+
+	let ambiguous = |prefix: &str| Error::AmbiguousPrefix {
+		prefix: prefix.to_string(),
+		existing: "usr_".to_string(),
+	};
+	assert_eq!(registry.register("usr_", Sqids::default()), Err(ambiguous("usr_")));
+	// `usr_a` works for a digit alphabet, but `usr_` IDs can start with `a` too
+	let digits =
+		Sqids::new(Some(Options::new(Some("0123456789".to_string()), None, None))).unwrap();
+	assert_eq!(registry.register("usr_a", digits.clone()), Err(ambiguous("usr_a")));
+	// unlike `usr__`, since `_` is never part of a `usr_` ID
+	assert!(registry.register("usr__", digits).is_ok());
+	assert_eq!(registry.register("usr", Sqids::default()), Err(Error::InvalidPrefix));
+
+	// `decode` skips group joiners, so they can't end a prefix or tell two apart either
+	let grouped =
+		Sqids::new(Some(Options { group: Some((4, '-')), ..Options::default() })).unwrap();
+	assert_eq!(registry.register("grp-", grouped.clone()), Err(Error::InvalidPrefix));
+	registry.register("grp_", grouped).unwrap();
+	assert_eq!(
+		registry.register("grp_-", Sqids::default()),
+		Err(Error::AmbiguousPrefix { prefix: "grp_-".to_string(), existing: "grp_".to_string() })
+	);
+}
+
+#[test]
+fn encode_prefixed() {
+	let sqids = Sqids::default();
+
+	let id = sqids.encode_prefixed("usr", &[1, 2, 3]).unwrap();
+	assert_eq!(id, "usr_86Rf07");
+	assert_eq!(sqids.decode_prefixed("usr", &id), Some(vec![1, 2, 3]));
+	assert_eq!(sqids.decode_prefixed("ord", &id), None);
+	assert_eq!(sqids.decode_prefixed("us", &id), None);
+	assert_eq!(sqids.decode_prefixed("usr", "usr_86R-07"), None);
+
+	assert_eq!(sqids.encode_prefixed("", &[1]), Err(Error::InvalidPrefix));
+	assert_eq!(sqids.encode_prefixed("usr_old", &[1]), Err(Error::InvalidPrefix));
+}
+
+#[test]
+fn encode_prefixed_separator() {
+	let sqids =
+		Sqids::new(Some(Options { prefix_separator: Some(':'), ..Options::default() })).unwrap();
+	let id = sqids.encode_prefixed("usr", &[1, 2, 3]).unwrap();
+	assert_eq!(id, "usr:86Rf07");
+	assert_eq!(sqids.decode_prefixed("usr", &id), Some(vec![1, 2, 3]));
+
+	assert_eq!(
+		Sqids::new(Some(Options { prefix_separator: Some('a'), ..Options::default() })).err(),
+		Some(Error::InvalidSeparator('a'))
+	);
+
+	let url_safe = Sqids::new(Some(Options::new(
+		Some("abcdefghijklmnopqrstuvwxyz0123456789-_".to_string()),
+		None,
+		None,
+	)))
+	.unwrap();
+	assert_eq!(url_safe.encode_prefixed("usr", &[1]), Err(Error::InvalidSeparator('_')));
+	assert_eq!(url_safe.decode_prefixed("usr", "usr_abc"), None);
+}
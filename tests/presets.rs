@@ -0,0 +1,58 @@
+use sqids::*;
+
+#[test]
+fn lowercase() {
+	let sqids = Sqids::lowercase(None);
+
+	assert_eq!(sqids.alphabet(), "abcdefghijklmnopqrstuvwxyz");
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "kxebeg");
+	assert_eq!(sqids.encode(&[1000]).unwrap(), "uebk");
+	assert_eq!(sqids.decode("kxebeg"), vec![1, 2, 3]);
+}
+
+#[test]
+fn uppercase() {
+	let sqids = Sqids::uppercase(None);
+
+	assert_eq!(sqids.alphabet(), "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "SXNZKL");
+	assert_eq!(sqids.encode(&[1000]).unwrap(), "QSVI");
+	assert_eq!(sqids.decode("SXNZKL"), vec![1, 2, 3]);
+}
+
+#[test]
+fn digits_and_upper() {
+	let sqids = Sqids::digits_and_upper(None);
+
+	assert_eq!(sqids.alphabet(), "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "YVOCNI");
+	assert_eq!(sqids.encode(&[1000]).unwrap(), "SEU");
+	assert_eq!(sqids.decode("YVOCNI"), vec![1, 2, 3]);
+}
+
+#[test]
+fn preset_with_min_length() {
+	let sqids = Sqids::lowercase(Some(10));
+
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "kxebegbfaz");
+	assert_eq!(sqids.decode("kxebegbfaz"), vec![1, 2, 3]);
+}
+
+#[cfg(feature = "default-blocklist")]
+#[test]
+fn presets_keep_default_blocklist() {
+	assert!(Sqids::lowercase(None).is_blocked("anus"));
+	assert!(Sqids::uppercase(None).is_blocked("ANUS"));
+	assert!(Sqids::digits_and_upper(None).is_blocked("ANUS"));
+}
+
+#[test]
+fn unambiguous() {
+	let sqids = Sqids::new(Some(presets::unambiguous())).unwrap();
+
+	for n in 0..10_000 {
+		let id = sqids.encode(&[n, n * 7]).unwrap();
+		assert!(!id.contains(['0', 'O', '1', 'l', 'I']), "{id}");
+		assert_eq!(sqids.decode(&id), vec![n, n * 7]);
+	}
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "regex")]
+
+use sqids::*;
+use std::collections::HashSet;
+
+fn with_patterns(patterns: &[&str]) -> Result<Sqids> {
+	Sqids::new(Some(Options {
+		blocklist: HashSet::new(),
+		blocklist_patterns: patterns.iter().map(|pattern| pattern.to_string()).collect(),
+		..Options::default()
+	}))
+}
+
+#[test]
+fn pattern_regenerates() {
+	// [1, 2, 3] is "86Rf07" without a blocklist
+	let sqids = with_patterns(&["[0-9]{2}$"]).unwrap();
+
+	assert!(sqids.is_blocked("86Rf07"));
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert_ne!(id, "86Rf07");
+	assert!(!sqids.is_blocked(&id));
+	assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+	assert_eq!(sqids.encode_with_stats(&[1, 2, 3]).unwrap().1, 2);
+}
+
+#[test]
+fn lowercased() {
+	let sqids = with_patterns(&["^86rf"]).unwrap();
+	assert!(sqids.is_blocked("86Rf07"));
+
+	let sensitive =
+		Sqids::new(Some(Options { blocklist_case_sensitive: true, ..sqids.options() })).unwrap();
+	assert!(!sensitive.is_blocked("86Rf07"));
+	assert!(sensitive.is_blocked("86rf07"));
+}
+
+#[test]
+fn invalid_pattern() {
+	assert!(matches!(
+		with_patterns(&["[0-9]", "("]).err().unwrap(),
+		Error::InvalidPattern { pattern, .. } if pattern == "("
+	));
+}
+
+#[test]
+fn options_and_fingerprint() {
+	let sqids = with_patterns(&["^x", "y$"]).unwrap();
+
+	assert_eq!(sqids.options().blocklist_patterns, vec!["^x", "y$"]);
+	assert_ne!(sqids.config_fingerprint(), with_patterns(&[]).unwrap().config_fingerprint());
+	assert_ne!(
+		with_patterns(&["^x"]).unwrap().config_fingerprint(),
+		with_patterns(&["^x", "y$"]).unwrap().config_fingerprint()
+	);
+}
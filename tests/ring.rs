@@ -0,0 +1,66 @@
+use sqids::*;
+
+fn ring() -> (SqidsRing, Sqids) {
+	let old = Sqids::default();
+	let new = Sqids::new(Some(Options { seed: Some(1), ..Options::default() })).unwrap();
+	(SqidsRing::new(new, [old.clone()]), old)
+}
+
+#[test]
+fn encodes_with_primary() {
+	let (ring, _) = ring();
+
+	assert_eq!(ring.encode(&[1, 2, 3]).unwrap(), ring.primary().encode(&[1, 2, 3]).unwrap());
+	assert_eq!(ring.configs().len(), 2);
+}
+
+#[test]
+fn decodes_with_first_matching_config() {
+	let (ring, old) = ring();
+
+	let id = ring.encode(&[1, 2, 3]).unwrap();
+	assert_eq!(ring.decode(&id), Some((vec![1, 2, 3], 0)));
+
+	// the old ID also "decodes" under the new alphabet, but isn't canonical there
+	let old_id = old.encode(&[1, 2, 3]).unwrap();
+	assert!(!ring.primary().decode(&old_id).is_empty());
+	assert_eq!(ring.decode(&old_id), Some((vec![1, 2, 3], 1)));
+
+	assert_eq!(ring.decode("*"), None);
+}
+
+#[test]
+fn decode_canonical() {
+	let sqids = Sqids::new(Some(Options::new(None, Some(10), None))).unwrap();
+	let id = sqids.encode(&[1, 2]).unwrap();
+
+	assert_eq!(sqids.decode_canonical(&id), Ok(vec![1, 2]));
+	assert_eq!(sqids.decode_canonical(&id[..id.len() - 1]), Err(Error::InvalidId));
+	assert_eq!(sqids.decode_canonical(""), Err(Error::InvalidId));
+}
+
+#[test]
+fn re_encode() {
+	let (ring, old) = ring();
+	let new = ring.primary();
+
+	let old_id = old.encode(&[1, 2, 3]).unwrap();
+	let new_id = old.re_encode(&old_id, new).unwrap();
+	assert_eq!(new_id, new.encode(&[1, 2, 3]).unwrap());
+
+	// garbage isn't converted
+	assert_eq!(old.re_encode("*", new), Err(Error::InvalidId));
+
+	// the target's encoding errors are passed through
+	let short = Sqids::new(Some(Options { max_length: Some(3), ..Options::default() })).unwrap();
+	assert_eq!(old.re_encode(&old_id, &short), Err(Error::MaxLengthExceeded));
+}
+
+#[test]
+fn re_encode_all() {
+	let (ring, old) = ring();
+	let ids = vec![old.encode(&[1]).unwrap(), "*".to_string(), old.encode(&[2, 3]).unwrap()];
+
+	let results: Vec<_> = old.re_encode_all(&ids, ring.primary()).collect();
+	assert_eq!(results, vec![ring.encode(&[1]), Err(Error::InvalidId), ring.encode(&[2, 3])]);
+}
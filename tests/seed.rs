@@ -0,0 +1,118 @@
+use sqids::*;
+
+#[test]
+fn from_seed() {
+	let sqids = Sqids::from_seed("secret", None).unwrap();
+
+	// pinned: the seeded permutation must never change between releases
+	assert_eq!(sqids.alphabet(), "irqvFoIB1j0pHZkEshyJmNKbS85uWdPwg9taeOxX34lDRAMcVLU26zfQn7YTGC");
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "mQoyoi");
+	assert_eq!(sqids.decode("mQoyoi"), vec![1, 2, 3]);
+}
+
+#[test]
+fn from_seed_with_base_alphabet() {
+	let sqids = Sqids::from_seed("secret", Some("0123456789abcdef")).unwrap();
+
+	assert_eq!(sqids.alphabet(), "32a9847c5fb0de61");
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "42e0ce");
+}
+
+#[test]
+fn same_seed_same_ids() {
+	let a = Sqids::from_seed("secret", None).unwrap();
+	let b = Sqids::from_seed("secret", None).unwrap();
+	let other = Sqids::from_seed("secret2", None).unwrap();
+
+	assert_eq!(a.encode(&[1, 2, 3]).unwrap(), b.encode(&[1, 2, 3]).unwrap());
+	assert_ne!(a.encode(&[1, 2, 3]).unwrap(), other.encode(&[1, 2, 3]).unwrap());
+}
+
+#[test]
+fn empty_seed_keeps_base_alphabet() {
+	assert_eq!(Sqids::from_seed("", None).unwrap().encode(&[1, 2, 3]).unwrap(), "86Rf07");
+}
+
+#[test]
+fn from_seed_invalid_base_alphabet() {
+	assert_eq!(Sqids::from_seed("secret", Some("ab")).err().unwrap(), Error::AlphabetLength);
+}
+
+#[test]
+fn seed_option() {
+	let seeded =
+		|seed| Sqids::new(Some(Options { seed: Some(seed), ..Options::default() })).unwrap();
+
+	// pinned: seeded IDs must never change between releases
+	assert_eq!(seeded(1).encode(&[1, 2, 3]).unwrap(), "axE7PW");
+	assert_eq!(seeded(1).encode(&[0]).unwrap(), "NT");
+	assert_eq!(seeded(42).encode(&[1, 2, 3]).unwrap(), "rLNov1");
+	assert_eq!(seeded(42).decode("rLNov1"), vec![1, 2, 3]);
+
+	// the original alphabet is kept, and other seeds decode to different numbers
+	assert_eq!(seeded(42).alphabet(), alphabet::DEFAULT);
+	assert_ne!(seeded(1).decode("rLNov1"), vec![1, 2, 3]);
+	assert_ne!(Sqids::default().decode("rLNov1"), vec![1, 2, 3]);
+	assert_ne!(seeded(1).config_fingerprint(), seeded(42).config_fingerprint());
+}
+
+#[test]
+fn derive_for_tenant() {
+	let base = Sqids::default();
+	let a = base.derive_for_tenant(1);
+	let b = base.derive_for_tenant(2);
+
+	// pinned: tenant IDs must never change between releases
+	assert_eq!(a.encode(&[1, 2, 3]).unwrap(), "axE7PW");
+	assert_eq!(b.encode(&[1, 2, 3]).unwrap(), "oc9cZ7");
+	let seeded = Sqids::new(Some(Options { seed: Some(7), ..Options::default() })).unwrap();
+	assert_eq!(seeded.derive_for_tenant(1).encode(&[1, 2, 3]).unwrap(), "FwCIDw");
+
+	// tenants can't read each other's IDs
+	for numbers in [vec![1, 2, 3], vec![0], vec![u64::MAX, 42]] {
+		let id = a.encode(&numbers).unwrap();
+		assert_eq!(a.decode(&id), numbers);
+		assert_ne!(b.decode(&id), numbers);
+		assert_ne!(base.decode(&id), numbers);
+	}
+
+	// the blocklist is shared, and the options rebuild the same tenant
+	#[cfg(feature = "blocklist")]
+	assert!(std::ptr::eq(a.blocklist(), base.blocklist()));
+	assert_eq!(Sqids::new(Some(a.options())).unwrap().encode(&[1, 2, 3]).unwrap(), "axE7PW");
+}
+
+#[test]
+fn for_tenant() {
+	let base = Sqids::default();
+	// one prefix extending the other is fine too
+	let tenants = [base.for_tenant(1, "t1_").unwrap(), base.for_tenant(2, "t1_x_").unwrap()];
+
+	for (i, tenant) in tenants.iter().enumerate() {
+		let other = &tenants[1 - i];
+		for number in (0..1000).chain([u64::MAX]) {
+			let id = tenant.encode(&[number]).unwrap();
+			assert_eq!(tenant.decode_one(&id), Ok(number));
+			assert!(tenant.validate_id(&id).is_ok());
+
+			assert!(other.try_decode(&id).is_err(), "{id}");
+			assert!(other.decode_one(&id).is_err(), "{id}");
+			assert!(other.validate_id(&id).is_err(), "{id}");
+			assert!(other.decode(&id).is_empty(), "{id}");
+		}
+	}
+
+	assert_eq!(tenants[0].options().seed, Some(1));
+	assert_eq!(base.for_tenant(1, "t1").err().unwrap(), Error::InvalidPrefix);
+	assert_eq!(base.for_tenant(1, "").err().unwrap(), Error::InvalidPrefix);
+
+	let grouped =
+		Sqids::new(Some(Options { group: Some((4, '-')), ..Options::default() })).unwrap();
+	assert_eq!(grouped.for_tenant(1, "t1-").err().unwrap(), Error::InvalidPrefix);
+	let lookalikes = Sqids::new(Some(Options {
+		homoglyph_map: Some(default_homoglyph_map()),
+		..Options::new(Some(alphabet::NO_LOOKALIKES.to_string()), None, None)
+	}))
+	.unwrap();
+	assert_eq!(lookalikes.for_tenant(1, "t0").err().unwrap(), Error::InvalidPrefix);
+}
@@ -0,0 +1,52 @@
+use sqids::*;
+
+fn with_rounds(shuffle_rounds: u8, alphabet: Option<String>) -> Result<Sqids> {
+	Sqids::new(Some(Options {
+		shuffle_rounds,
+		allow_multibyte: true,
+		..Options::new(alphabet, Some(20), None)
+	}))
+}
+
+#[test]
+fn roundtrip() {
+	// more than 128 characters, so nothing is precomputed
+	let long: String = ('\u{4e00}'..='\u{4ec7}').collect();
+
+	for alphabet in [None, Some("0123456789abcdef".to_string()), Some(long)] {
+		for rounds in [1, 2, 3, 7] {
+			let sqids = with_rounds(rounds, alphabet.clone()).unwrap();
+
+			for numbers in [vec![0], vec![1, 2, 3], vec![u64::MAX, 0, 42, 7]] {
+				let id = sqids.encode(&numbers).unwrap();
+				assert_eq!(sqids.decode(&id), numbers, "{rounds} rounds");
+			}
+		}
+	}
+}
+
+#[test]
+fn one_round_is_the_spec() {
+	let sqids = with_rounds(1, None).unwrap();
+	let spec = Sqids::new(Some(Options::new(None, Some(20), None))).unwrap();
+
+	assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), spec.encode(&[1, 2, 3]).unwrap());
+	assert_eq!(sqids.config_fingerprint(), spec.config_fingerprint());
+}
+
+#[test]
+fn more_rounds_change_ids() {
+	let one = with_rounds(1, None).unwrap();
+	let two = with_rounds(2, None).unwrap();
+
+	// the first number is encoded before any shuffle
+	let (a, b) = (one.encode(&[1, 2, 3]).unwrap(), two.encode(&[1, 2, 3]).unwrap());
+	assert_eq!(a[..2], b[..2]);
+	assert_ne!(a, b);
+	assert_ne!(one.config_fingerprint(), two.config_fingerprint());
+}
+
+#[test]
+fn zero_rounds() {
+	assert_eq!(with_rounds(0, None).err().unwrap(), Error::InvalidShuffleRounds);
+}
@@ -0,0 +1,72 @@
+#![cfg(all(feature = "tracing", feature = "blocklist"))]
+
+use sqids::*;
+use std::{
+	collections::HashSet,
+	sync::{
+		atomic::{AtomicU64, AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
+};
+use tracing::{
+	field::{Field, Visit},
+	span, Event, Metadata, Subscriber,
+};
+
+/// Counts events and collects the `retries` recorded on spans.
+#[derive(Clone, Default)]
+struct Recorder {
+	next_id: Arc<AtomicU64>,
+	events: Arc<AtomicUsize>,
+	retries: Arc<Mutex<Vec<u64>>>,
+}
+
+impl Visit for Recorder {
+	fn record_u64(&mut self, field: &Field, value: u64) {
+		if field.name() == "retries" {
+			self.retries.lock().unwrap().push(value);
+		}
+	}
+
+	fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+}
+
+impl Subscriber for Recorder {
+	fn enabled(&self, _: &Metadata<'_>) -> bool {
+		true
+	}
+
+	fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+		span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+	}
+
+	fn record(&self, _: &span::Id, values: &span::Record<'_>) {
+		values.record(&mut self.clone());
+	}
+
+	fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+	fn event(&self, _: &Event<'_>) {
+		self.events.fetch_add(1, Ordering::Relaxed);
+	}
+
+	fn enter(&self, _: &span::Id) {}
+
+	fn exit(&self, _: &span::Id) {}
+}
+
+#[test]
+fn records_blocklist_retries() {
+	let recorder = Recorder::default();
+	let sqids =
+		Sqids::new(Some(Options::new(None, None, Some(HashSet::from(["aho1e".to_owned()])))))
+			.unwrap();
+
+	tracing::subscriber::with_default(recorder.clone(), || {
+		assert_eq!(sqids.encode(&[4572721]).unwrap(), "JExTR");
+		assert_eq!(sqids.encode(&[1, 2, 3]).unwrap(), "86Rf07");
+	});
+
+	assert_eq!(*recorder.retries.lock().unwrap(), vec![1, 0]);
+	assert_eq!(recorder.events.load(Ordering::Relaxed), 1);
+}
@@ -0,0 +1,76 @@
+use sqids::*;
+
+#[test]
+fn vectors_roundtrip() {
+	let sqids = Sqids::default();
+	let spec = VectorSpec { count: 50, min_lengths: vec![20], ..VectorSpec::default() };
+	let vectors = sqids.generate_test_vectors(spec.clone());
+
+	assert_eq!(vectors, sqids.generate_test_vectors(spec));
+	for vector in &vectors {
+		assert!(spec_contains(&vector.numbers));
+		match vector.min_length {
+			Some(min_length) => {
+				assert_eq!(
+					sqids.encode_with_min_length(&vector.numbers, min_length).unwrap(),
+					vector.id
+				);
+				assert!(vector.id.len() >= min_length as usize);
+			}
+			None => assert_eq!(sqids.encode(&vector.numbers).unwrap(), vector.id),
+		}
+		assert_eq!(sqids.decode(&vector.id), vector.numbers);
+	}
+	assert_eq!(vectors.iter().filter(|vector| vector.min_length == Some(20)).count(), 50);
+}
+
+fn spec_contains(numbers: &[u64]) -> bool {
+	(1..=5).contains(&numbers.len()) && numbers.iter().all(|n| (0..=1_000_000).contains(n))
+}
+
+#[test]
+fn vectors_are_pinned() {
+	let vectors = Sqids::default().generate_test_vectors(VectorSpec {
+		count: 2,
+		blocklist_cases: 0,
+		..VectorSpec::default()
+	});
+
+	// pinned: a spec must always name the same corpus
+	assert_eq!(
+		vectors,
+		vec![
+			TestVector { numbers: vec![793785], id: "U5aqB".to_string(), min_length: None },
+			TestVector {
+				numbers: vec![75257, 854070, 598916, 488206, 713848],
+				id: "wbRxfQ9lxJPyeGnhdUD3MtTf".to_string(),
+				min_length: None
+			},
+		]
+	);
+}
+
+#[cfg(feature = "default-blocklist")]
+#[test]
+fn blocklist_cases() {
+	let sqids = Sqids::default();
+	let vectors = sqids.generate_test_vectors(VectorSpec {
+		count: 0,
+		values: 250_000..=1_000_000,
+		blocklist_cases: 3,
+		..VectorSpec::default()
+	});
+
+	assert_eq!(vectors.len(), 3);
+	for vector in vectors {
+		assert!(sqids.encode_with_stats(&vector.numbers).unwrap().1 > 1);
+	}
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn vectors_serialize() {
+	let vector = TestVector { numbers: vec![1, 2, 3], id: "86Rf07".to_string(), min_length: None };
+
+	assert_eq!(serde_json::to_string(&vector).unwrap(), r#"{"numbers":[1,2,3],"id":"86Rf07"}"#);
+}
@@ -0,0 +1,106 @@
+use sqids::*;
+
+fn versioned() -> VersionedSqids {
+	let v0 = Sqids::default();
+	let v1 =
+		Sqids::new(Some(Options { seed: Some(1), ..Options::new(None, Some(8), None) })).unwrap();
+	VersionedSqids::new(1, [(0, v0), (1, v1)]).unwrap()
+}
+
+#[test]
+fn versioned_roundtrip() {
+	let sqids = versioned();
+
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert!(id.starts_with('b'));
+	assert_eq!(sqids.decode(&id), Ok((1, vec![1, 2, 3])));
+
+	// IDs of older versions keep decoding with their own configuration
+	let old = format!("a{}", Sqids::default().encode(&[1, 2, 3]).unwrap());
+	assert_eq!(sqids.decode(&old), Ok((0, vec![1, 2, 3])));
+}
+
+#[test]
+fn version_counts_towards_min_length() {
+	let sqids = versioned();
+
+	for numbers in [vec![0], vec![1], vec![1, 2]] {
+		let id = sqids.encode(&numbers).unwrap();
+		assert_eq!(id.len(), 8);
+		assert_eq!(sqids.decode(&id), Ok((1, numbers)));
+	}
+
+	// longer IDs are one character longer than untagged ones
+	let numbers = [u64::MAX, u64::MAX];
+	let untagged = sqids.get(1).unwrap().encode(&numbers).unwrap();
+	assert_eq!(sqids.encode(&numbers).unwrap().len(), untagged.len() + 1);
+}
+
+#[test]
+fn unknown_versions() {
+	let sqids = versioned();
+
+	assert_eq!(sqids.decode("c86Rf07"), Err(Error::UnknownVersion('c')));
+	assert_eq!(sqids.decode("-86Rf07"), Err(Error::UnknownVersion('-')));
+	assert_eq!(sqids.decode("a"), Err(Error::InvalidId));
+	assert_eq!(sqids.decode(""), Err(Error::InvalidId));
+
+	assert_eq!(
+		VersionedSqids::new(2, [(0, Sqids::default())]).err().unwrap(),
+		Error::MissingVersion(2)
+	);
+	assert_eq!(
+		VersionedSqids::new(0, [(62, Sqids::default())]).err().unwrap(),
+		Error::VersionOutOfRange(62)
+	);
+}
+
+#[test]
+fn tags_stay_within_alphabet() {
+	let hex = || Sqids::new(Some(Options::new(Some("0123456789abcdef".to_string()), None, None)));
+	let sqids = VersionedSqids::new(12, [(3, hex().unwrap()), (12, hex().unwrap())]).unwrap();
+
+	let id = sqids.encode(&[1, 2]).unwrap();
+	assert!(id.starts_with('c'));
+	assert!(id.chars().all(|c| c.is_ascii_hexdigit()), "{id}");
+	assert_eq!(sqids.decode(&id), Ok((12, vec![1, 2])));
+
+	assert_eq!(
+		VersionedSqids::new(0, [(16, hex().unwrap())]).err().unwrap(),
+		Error::VersionOutOfRange(16)
+	);
+	// version 0 of "bcd..." and version 1 of "abc..." are both tagged `b`
+	let other = Sqids::new(Some(Options::new(Some("bcdefghij".to_string()), None, None)));
+	let ambiguous = Sqids::new(Some(Options::new(Some("abcdefghi".to_string()), None, None)));
+	assert_eq!(
+		VersionedSqids::new(0, [(0, other.unwrap()), (1, ambiguous.unwrap())]).err().unwrap(),
+		Error::AmbiguousVersionTag { tag: 'b', first: 0, second: 1 }
+	);
+}
+
+#[test]
+fn version_counts_towards_fixed_length() {
+	let v0 = Sqids::new(Some(Options { fixed_length: Some(8), ..Options::default() })).unwrap();
+	let sqids = VersionedSqids::new(0, [(0, v0)]).unwrap();
+
+	for numbers in [vec![0], vec![1, 2, 3], vec![1_000_000]] {
+		let id = sqids.encode(&numbers).unwrap();
+		assert_eq!(id.len(), 8, "{id}");
+		assert_eq!(sqids.decode(&id), Ok((0, numbers)));
+	}
+	assert_eq!(sqids.encode(&[u64::MAX]).err().unwrap(), Error::MaxLengthExceeded);
+}
+
+#[test]
+fn versioned_trim_input() {
+	let trimmed = Sqids::new(Some(Options { trim_input: true, ..Options::default() })).unwrap();
+	let sqids = VersionedSqids::new(0, [(0, trimmed)]).unwrap();
+
+	let id = sqids.encode(&[1, 2, 3]).unwrap();
+	assert_eq!(sqids.decode(&format!(" {id}\n")), Ok((0, vec![1, 2, 3])));
+	assert_eq!(sqids.decode(" c86Rf07"), Err(Error::UnknownVersion('c')));
+	assert_eq!(sqids.decode("  "), Err(Error::InvalidId));
+
+	// without it, the whitespace is the tag
+	assert_eq!(versioned().decode(&format!(" {id}")), Err(Error::UnknownVersion(' ')));
+}
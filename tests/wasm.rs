@@ -0,0 +1,41 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use sqids::wasm::WasmSqids;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn simple() {
+	let sqids = WasmSqids::new(None, None).unwrap();
+
+	assert_eq!(sqids.encode(&[1.0, 2.0, 3.0]).unwrap(), "86Rf07");
+	assert_eq!(sqids.decode("86Rf07").unwrap(), vec![1.0, 2.0, 3.0]);
+}
+
+#[wasm_bindgen_test]
+fn custom_alphabet_and_min_length() {
+	let sqids = WasmSqids::new(Some("0123456789abcdef".to_string()), Some(8)).unwrap();
+
+	let id = sqids.encode(&[1.0, 2.0, 3.0]).unwrap();
+	assert_eq!(id.len(), 8);
+	assert_eq!(sqids.decode(&id).unwrap(), vec![1.0, 2.0, 3.0]);
+}
+
+#[wasm_bindgen_test]
+fn rejects_unsafe_numbers() {
+	let sqids = WasmSqids::new(None, None).unwrap();
+
+	assert!(sqids.encode(&[-1.0]).is_err());
+	assert!(sqids.encode(&[1.5]).is_err());
+	assert!(sqids.encode(&[f64::NAN]).is_err());
+	assert!(sqids.encode(&[9007199254740992.0]).is_err());
+	assert!(sqids.encode(&[9007199254740991.0]).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn bigint_covers_full_range() {
+	let sqids = WasmSqids::new(None, None).unwrap();
+
+	let id = sqids.encode_bigint(&[u64::MAX]).unwrap();
+	assert!(sqids.decode(&id).is_err());
+	assert_eq!(sqids.decode_bigint(&id), vec![u64::MAX]);
+}